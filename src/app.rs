@@ -1,58 +1,118 @@
 use crate::{
-    auction::Auction,
-    bids::preprocess_bids,
+    auction::{Auction, AuctionParams},
+    bids::{preprocess_bids, resolve_bids},
     blocks::{BlockConsumer, BlockProducer, Completion, ShutdownReason},
     config::Config,
     logging::{log_summary, persist_summary},
-    registry::{BidRegistry, BidSummary},
-    validate::PreflightValidator,
-};
-use alloy::{
-    primitives::{Address, address},
-    providers::Provider,
+    registry::{BidRegistry, BidSummary, PlannedBid},
+    validate::{PreflightValidator, SenderBudget},
 };
+use alloy::providers::Provider;
 use eyre::Result;
 use futures_util::StreamExt;
 use tracing::{error, info, instrument, warn};
 
-const CCA_ADDRESS: Address = address!("0x608c4e792C65f5527B3f70715deA44d3b302F4Ee");
-const HOOK_ADDRESS: Address = address!("0x2DD6e0E331DE9743635590F6c8BC5038374CAc9D");
-const SOULBOUND_ADDRESS: Address = address!("0xBf3CF56c587F5e833337200536A52E171EF29A09");
-
-pub struct AuctionBot<P>
+/// Auction contracts, on-chain parameters and validated bids shared by every command.
+pub struct AuctionSetup<P>
 where
-    P: Provider + Clone + Unpin,
+    P: Provider + Clone,
 {
-    block_producer: BlockProducer<P>,
-    block_consumer: BlockConsumer<P>,
+    pub auction: Auction<P>,
+    pub params: AuctionParams,
+    pub planned_bids: Vec<PlannedBid>,
+    pub budgets: Vec<SenderBudget>,
 }
 
-impl<P> AuctionBot<P>
+impl<P> AuctionSetup<P>
 where
-    P: Provider + Clone + Send + Sync + Unpin + 'static,
+    P: Provider + Clone,
 {
-    pub async fn build_with_provider(provider: P, config: Config) -> Result<Self> {
-        info!(bids = config.bids.len(), "configuration loaded");
+    pub async fn connect(provider: P, config: &Config) -> Result<Auction<P>> {
+        let network = &config.network;
+        info!(
+            bids = config.bids.len(),
+            ladders = config.ladders.len(),
+            network = %network.network,
+            cca = ?network.cca,
+            "configuration loaded"
+        );
 
-        let auction = Auction::new(
-            provider.clone(),
-            CCA_ADDRESS,
-            HOOK_ADDRESS,
-            SOULBOUND_ADDRESS,
+        for sender in config.senders.iter() {
+            info!(%sender, "signer loaded");
+        }
+
+        let chain_id = provider.get_chain_id().await?;
+        network.ensure_chain_id(chain_id)?;
+
+        let auction = Auction::new(provider, network.cca).await?;
+        network.ensure_contracts(
+            *auction.validation_hook.address(),
+            *auction.soulbound.address(),
+        )?;
+        info!(
+            hook = ?auction.validation_hook.address(),
+            soulbound = ?auction.soulbound.address(),
+            token = ?auction.token,
+            currency = ?auction.currency,
+            "auction contracts discovered"
         );
-        let params = auction.load_params(config.signer.address()).await?;
+        Ok(auction)
+    }
 
-        PreflightValidator::new(&params, &config.bids).run()?;
+    pub async fn load(provider: P, config: &Config) -> Result<Self> {
+        let auction = Self::connect(provider, config).await?;
+        let params = auction.load_params(&config.senders.addresses()).await?;
 
-        let planned_bids = preprocess_bids(&config.bids, &params);
+        let bids = resolve_bids(&config.bids, &config.ladders, &params)?;
+        let budgets = PreflightValidator::new(&params, &bids).run()?;
+        for budget in &budgets {
+            info!(
+                sender = ?budget.sender,
+                bids = budget.labels.len(),
+                purchased = %budget.total_purchased,
+                planned = %budget.planned,
+                remaining = %budget.remaining(),
+                "sender allocation checked"
+            );
+        }
+
+        let planned_bids = preprocess_bids(&bids);
 
-        let registry = BidRegistry::new(
+        Ok(Self {
             auction,
             params,
             planned_bids,
-            config.signer.clone(),
-            CCA_ADDRESS,
-        )?;
+            budgets,
+        })
+    }
+
+    pub fn into_registry(self, config: &Config) -> Result<BidRegistry<P>> {
+        BidRegistry::new(
+            self.auction,
+            self.params,
+            self.planned_bids,
+            &config.senders,
+            config.network.cca,
+        )
+    }
+}
+
+pub struct AuctionBot<P>
+where
+    P: Provider + Clone + Unpin,
+{
+    block_producer: BlockProducer<P>,
+    block_consumer: BlockConsumer<P>,
+}
+
+impl<P> AuctionBot<P>
+where
+    P: Provider + Clone + Send + Sync + Unpin + 'static,
+{
+    pub async fn build_with_provider(provider: P, config: Config) -> Result<Self> {
+        let registry = AuctionSetup::load(provider.clone(), &config)
+            .await?
+            .into_registry(&config)?;
 
         let block_producer = BlockProducer::new(provider.clone(), &config.transport).await?;
         let block_consumer = BlockConsumer::new(registry);
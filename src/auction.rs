@@ -1,12 +1,33 @@
 use crate::{
-    CCA::CCAInstance, Soulbound::SoulboundInstance, ValidationHook::ValidationHookInstance,
+    CCA::{self, CCAInstance},
+    ERC20::ERC20Instance,
+    Soulbound::SoulboundInstance,
+    ValidationHook::ValidationHookInstance,
     config::BidParams,
+    ticks::TickCache,
+    units::{CurrencyInfo, PriceScale, check_decimals},
 };
 use alloy::{
     primitives::{Address, U256},
     providers::Provider,
+    rpc::types::Filter,
+    sol_types::SolEvent,
 };
 use eyre::{Result, eyre};
+use futures_util::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+use tracing::{info, warn};
+
+/// Sentinel `next` pointer of the highest initialized tick.
+const MAX_TICK_PTR: U256 = U256::MAX;
+
+/// Milli-basis points in the whole supply schedule (`ConstantsLib.MPS`).
+const MPS: u32 = 10_000_000;
 
 #[derive(Debug, Clone)]
 pub struct Auction<P>
@@ -17,31 +38,56 @@ where
     pub cca: CCAInstance<P>,
     pub validation_hook: ValidationHookInstance<P>,
     pub soulbound: SoulboundInstance<P>,
+    pub token: Address,
+    pub currency: Address,
+    /// Shared by every clone, so all bids read and refresh the same cache.
+    ticks: Arc<RwLock<TickCache>>,
 }
 
 impl<P> Auction<P>
 where
     P: Provider + Clone,
 {
-    pub fn new(
-        provider: P,
-        cca_addr: Address,
-        hook_addr: Address,
-        soulbound_addr: Address,
-    ) -> Self {
+    /// Resolves the hook, soulbound, token and currency contracts from the CCA itself.
+    /// A zero currency address denotes native ETH.
+    pub async fn new(provider: P, cca_addr: Address) -> Result<Self> {
         let cca = CCAInstance::new(cca_addr, provider.clone());
+
+        let (hook_addr, token, currency) = provider
+            .multicall()
+            .add(cca.validationHook())
+            .add(cca.token())
+            .add(cca.currency())
+            .aggregate()
+            .await?;
+
+        if hook_addr == Address::ZERO {
+            return Err(eyre!(
+                "auction {cca_addr} has no validation hook configured"
+            ));
+        }
+
         let validation_hook = ValidationHookInstance::new(hook_addr, provider.clone());
+        let soulbound_addr = validation_hook.SOULBOUND().call().await?;
+        if soulbound_addr == Address::ZERO {
+            return Err(eyre!(
+                "validation hook {hook_addr} of auction {cca_addr} has no soulbound token configured"
+            ));
+        }
         let soulbound = SoulboundInstance::new(soulbound_addr, provider.clone());
 
-        Self {
+        Ok(Self {
             provider,
             cca,
             validation_hook,
             soulbound,
-        }
+            token,
+            currency,
+            ticks: Arc::default(),
+        })
     }
 
-    pub async fn load_params(&self, signer_address: Address) -> Result<AuctionParams> {
+    pub async fn load_params(&self, senders: &[Address]) -> Result<AuctionParams> {
         let multicall = self
             .provider
             .multicall()
@@ -50,9 +96,10 @@ where
             .add(self.cca.floorPrice())
             .add(self.cca.tickSpacing())
             .add(self.cca.MAX_BID_PRICE())
+            .add(self.cca.startBlock())
             .add(self.cca.endBlock())
-            .add(self.validation_hook.totalPurchased(signer_address))
-            .add(self.soulbound.hasAnyToken(signer_address));
+            .add(self.cca.claimBlock())
+            .add(ERC20Instance::new(self.token, &self.provider).decimals());
 
         let (
             contributor_period_end_block,
@@ -60,85 +107,718 @@ where
             floor_price,
             tick_spacing,
             max_bid_price,
+            start_block_raw,
             end_block_raw,
-            total_purchased,
-            has_any_token,
+            claim_block_raw,
+            token_decimals,
         ) = multicall.aggregate().await?;
 
+        let start_block = U256::from(start_block_raw);
         let end_block = U256::from(end_block_raw);
+        let claim_block = U256::from(claim_block_raw);
+        let allocations =
+            try_join_all(senders.iter().map(|sender| self.sender_allocation(*sender))).await?;
+        let currency = self.currency_info().await?;
+        let price_scale = PriceScale {
+            token_decimals: check_decimals(&format!("token {}", self.token), token_decimals)?,
+            currency_decimals: currency.decimals,
+        };
 
         Ok(AuctionParams {
+            start_block,
             contributor_period_end_block,
             max_purchase_limit,
             floor_price,
             tick_spacing,
             max_bid_price,
             end_block,
+            claim_block,
+            allocations,
+            price_scale,
+            currency,
+        })
+    }
+
+    /// Re-reads the values that move while the auction runs, once for all bids.
+    pub async fn load_state(&self, senders: &[Address], block: u64) -> Result<AuctionState> {
+        let (clearing_price, currency_raised, total_supply, checkpoint, checkpoint_block) = self
+            .provider
+            .multicall()
+            .add(self.cca.clearingPrice())
+            .add(self.cca.currencyRaised())
+            .add(self.cca.totalSupply())
+            .add(self.cca.latestCheckpoint())
+            .add(self.cca.lastCheckpointedBlock())
+            .aggregate()
+            .await?;
+        let allocations =
+            try_join_all(senders.iter().map(|sender| self.sender_allocation(*sender))).await?;
+
+        Ok(AuctionState {
+            block,
+            clearing_price,
+            currency_raised,
+            total_supply,
+            checkpoint: CheckpointInfo {
+                block: checkpoint_block,
+                clearing_price: checkpoint.clearingPrice,
+                cumulative_mps: checkpoint.cumulativeMps.to::<u32>(),
+            },
+            allocations,
+        })
+    }
+
+    async fn sender_allocation(&self, sender: Address) -> Result<SenderAllocation> {
+        let (total_purchased, has_any_token) = self
+            .provider
+            .multicall()
+            .add(self.validation_hook.totalPurchased(sender))
+            .add(self.soulbound.hasAnyToken(sender))
+            .aggregate()
+            .await?;
+        Ok(SenderAllocation {
+            sender,
             total_purchased,
             has_any_token,
         })
     }
 
+    async fn currency_info(&self) -> Result<CurrencyInfo> {
+        if self.currency == Address::ZERO {
+            return Ok(CurrencyInfo::native());
+        }
+        let erc20 = ERC20Instance::new(self.currency, &self.provider);
+        let (symbol, decimals) = self
+            .provider
+            .multicall()
+            .add(erc20.symbol())
+            .add(erc20.decimals())
+            .aggregate()
+            .await?;
+        Ok(CurrencyInfo {
+            decimals: check_decimals(&format!("currency {symbol}"), decimals)?,
+            symbol,
+            native: false,
+        })
+    }
+
     pub async fn compute_prev_tick_price(
         &self,
-        params: &AuctionParams,
+        floor_price: U256,
         bid_price: U256,
     ) -> Result<U256> {
-        let floor_price = params.floor_price;
+        if self.ticks_read().next_block().is_none() {
+            self.load_ticks(floor_price).await?;
+        }
+        self.ticks_read().prev_tick(floor_price, bid_price)
+    }
 
-        if bid_price < floor_price {
-            return Err(eyre!(
-                "bid price {} is below floor price {}",
-                bid_price,
-                floor_price
-            ));
+    /// `prevTickPrice` hints for `prices`, returned in the same order. Cached
+    /// hints are checked together in one `ticks()` multicall; without a usable
+    /// cache, the bids are sorted by price and served by a single pass over the
+    /// tick list.
+    pub async fn prev_tick_prices(&self, floor_price: U256, prices: &[U256]) -> Result<Vec<U256>> {
+        if prices.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if self.ticks_read().next_block().is_some() {
+            let hints = prices
+                .iter()
+                .map(|price| self.ticks_read().prev_tick(floor_price, *price))
+                .collect::<Result<Vec<_>>>()?;
+            if self.hints_current(prices, &hints).await? {
+                return Ok(hints);
+            }
+            warn!("cached prevTickPrice hints are stale, walking the tick list");
+            self.ticks_write().invalidate();
         }
 
+        self.walk_prev_ticks(floor_price, prices).await
+    }
+
+    /// True if every hint is still an initialized tick directly below its price.
+    async fn hints_current(&self, prices: &[U256], hints: &[U256]) -> Result<bool> {
+        let distinct: Vec<U256> = hints
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let ticks = self
+            .provider
+            .multicall()
+            .dynamic::<CCA::ticksCall>()
+            .extend(distinct.iter().map(|hint| self.cca.ticks(*hint)))
+            .aggregate()
+            .await?;
+        let next: BTreeMap<U256, U256> = distinct
+            .into_iter()
+            .zip(ticks.into_iter().map(|tick| tick.next))
+            .collect();
+
+        Ok(prices.iter().zip(hints).all(|(price, hint)| {
+            let next = next[hint];
+            next != U256::ZERO && (hint >= price || next >= *price)
+        }))
+    }
+
+    /// Walks the tick list from the floor once, in ascending bid-price order,
+    /// stopping at the highest bid.
+    async fn walk_prev_ticks(&self, floor_price: U256, prices: &[U256]) -> Result<Vec<U256>> {
+        let mut order: Vec<usize> = (0..prices.len()).collect();
+        order.sort_by_key(|&idx| prices[idx]);
+
+        let mut hints = vec![floor_price; prices.len()];
         let mut prev = floor_price;
-        let mut tick = self.cca.ticks(prev).call().await?;
-        let mut next = tick.next;
+        let mut next = self.cca.ticks(prev).call().await?.next;
+        for idx in order {
+            let price = prices[idx];
+            if price < floor_price {
+                return Err(eyre!(
+                    "bid price {price} is below floor price {floor_price}"
+                ));
+            }
+            while next < price && next > prev && next != MAX_TICK_PTR {
+                prev = next;
+                next = self.cca.ticks(prev).call().await?.next;
+            }
+            hints[idx] = prev;
+        }
+        Ok(hints)
+    }
 
-        while next < bid_price {
-            prev = next;
-            tick = self.cca.ticks(prev).call().await?;
-            next = tick.next;
+    /// Brings the tick cache up to `block` from `TickInitialized` logs, walking
+    /// the tick list instead if the cache has not been loaded yet.
+    pub async fn sync_ticks(&self, floor_price: U256, block: u64) -> Result<()> {
+        let Some(from_block) = self.ticks_read().next_block() else {
+            return self.load_ticks(floor_price).await;
+        };
+        if from_block > block {
+            return Ok(());
+        }
+
+        let filter = Filter::new()
+            .address(*self.cca.address())
+            .event_signature(CCA::TickInitialized::SIGNATURE_HASH)
+            .from_block(from_block)
+            .to_block(block);
+        let logs = match self.provider.get_logs(&filter).await {
+            Ok(logs) => logs,
+            Err(err) => {
+                self.ticks_write().invalidate();
+                return Err(eyre!("failed to fetch tick logs, cache dropped: {err}"));
+            }
+        };
+        let prices = logs
+            .iter()
+            .map(|log| Ok(log.log_decode::<CCA::TickInitialized>()?.inner.data.price))
+            .collect::<Result<Vec<_>>>()?;
+
+        if !prices.is_empty() {
+            info!(new_ticks = prices.len(), block, "tick cache updated");
+        }
+        self.ticks_write().apply(prices, block);
+        Ok(())
+    }
+
+    /// Walks the on-chain tick list once from the floor to seed the cache.
+    async fn load_ticks(&self, floor_price: U256) -> Result<()> {
+        let block = self.provider.get_block_number().await?;
+        let mut prices = vec![floor_price];
+        let mut price = floor_price;
+        loop {
+            let next = self.cca.ticks(price).call().await?.next;
+            if next <= price || next == MAX_TICK_PTR {
+                break;
+            }
+            prices.push(next);
+            price = next;
         }
 
-        Ok(prev)
+        let mut cache = self.ticks_write();
+        cache.load(prices, block);
+        info!(ticks = cache.len(), block, "tick cache loaded");
+        Ok(())
+    }
+
+    fn ticks_read(&self) -> RwLockReadGuard<'_, TickCache> {
+        self.ticks.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn ticks_write(&self) -> RwLockWriteGuard<'_, TickCache> {
+        self.ticks.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Test fixture: the CCA at `cca` with no hook, soulbound token or ticks loaded.
+    #[cfg(test)]
+    pub fn for_test(provider: P, cca: Address) -> Self {
+        Self {
+            cca: CCAInstance::new(cca, provider.clone()),
+            validation_hook: ValidationHookInstance::new(Address::ZERO, provider.clone()),
+            soulbound: SoulboundInstance::new(Address::ZERO, provider.clone()),
+            provider,
+            token: Address::ZERO,
+            currency: Address::ZERO,
+            ticks: Default::default(),
+        }
     }
 
     pub async fn prepare_submit_bid(
         &self,
         cfg: &BidParams,
-        params: &AuctionParams,
+        floor_price: U256,
         resolved_owner: Address,
+        prev_tick_hint: Option<U256>,
     ) -> Result<SubmitBidParams> {
-        let prev_tick_price = self.compute_prev_tick_price(params, cfg.max_bid).await?;
+        let prev_tick_price = match prev_tick_hint {
+            Some(hint) => hint,
+            None => {
+                self.compute_prev_tick_price(floor_price, cfg.max_bid)
+                    .await?
+            }
+        };
         Ok(SubmitBidParams {
             max_price: cfg.max_bid,
             amount: cfg.amount,
             owner: resolved_owner,
             prev_tick_price,
+            hintless: false,
         })
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuctionPhase {
+    /// Before `startBlock`; the CCA rejects every bid.
+    NotStarted,
+    ContributorTrack,
+    PublicTrack,
+    /// Bidding closed, tokens not yet claimable.
+    Ended,
+    /// From `claimBlock` on.
+    Claimable,
+}
+
+impl AuctionPhase {
+    /// No bid can be placed in this phase or any later one.
+    pub fn is_closed(self) -> bool {
+        matches!(self, Self::Ended | Self::Claimable)
+    }
+}
+
+impl fmt::Display for AuctionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NotStarted => "not started",
+            Self::ContributorTrack => "contributor track",
+            Self::PublicTrack => "public track",
+            Self::Ended => "ended",
+            Self::Claimable => "claimable",
+        };
+        f.write_str(name)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AuctionParams {
+    pub start_block: U256,
     pub contributor_period_end_block: U256,
     pub max_purchase_limit: U256,
     pub floor_price: U256,
     pub tick_spacing: U256,
     pub max_bid_price: U256,
     pub end_block: U256,
+    pub claim_block: U256,
+    /// Validation hook state for every loaded sender.
+    pub allocations: Vec<SenderAllocation>,
+    pub price_scale: PriceScale,
+    pub currency: CurrencyInfo,
+}
+
+impl AuctionParams {
+    pub fn window(&self) -> AuctionWindow {
+        AuctionWindow {
+            start_block: self.start_block,
+            contributor_period_end_block: self.contributor_period_end_block,
+            end_block: self.end_block,
+            claim_block: self.claim_block,
+        }
+    }
+
+    pub fn allocation(&self, sender: Address) -> Option<&SenderAllocation> {
+        self.allocations
+            .iter()
+            .find(|allocation| allocation.sender == sender)
+    }
+}
+
+/// Test fixture: a running auction with unit floor and spacing, no cap, an
+/// 18-decimal token and the native currency. Override fields with `with_*`.
+#[cfg(test)]
+impl AuctionParams {
+    pub fn for_test() -> Self {
+        Self {
+            start_block: U256::ZERO,
+            contributor_period_end_block: U256::ZERO,
+            max_purchase_limit: U256::ZERO,
+            floor_price: U256::from(1u64),
+            tick_spacing: U256::from(1u64),
+            max_bid_price: U256::MAX,
+            end_block: U256::from(1_000u64),
+            claim_block: U256::from(1_000u64),
+            allocations: Vec::new(),
+            price_scale: PriceScale {
+                token_decimals: 18,
+                currency_decimals: 18,
+            },
+            currency: CurrencyInfo::native(),
+        }
+    }
+
+    pub fn with_ticks(mut self, floor_price: U256, tick_spacing: U256) -> Self {
+        self.floor_price = floor_price;
+        self.tick_spacing = tick_spacing;
+        self
+    }
+
+    pub fn with_max_bid_price(mut self, max_bid_price: U256) -> Self {
+        self.max_bid_price = max_bid_price;
+        self
+    }
+
+    /// Sets the public track to `[contributor_period_end_block, end_block)`,
+    /// claimable from `end_block`.
+    pub fn with_blocks(mut self, contributor_period_end_block: u64, end_block: u64) -> Self {
+        self.contributor_period_end_block = U256::from(contributor_period_end_block);
+        self.end_block = U256::from(end_block);
+        self.claim_block = U256::from(end_block);
+        self
+    }
+
+    pub fn with_allocations(
+        mut self,
+        max_purchase_limit: U256,
+        allocations: Vec<SenderAllocation>,
+    ) -> Self {
+        self.max_purchase_limit = max_purchase_limit;
+        self.allocations = allocations;
+        self
+    }
+}
+
+/// Test fixture: `params` at `block`, clearing at `clearing_price` with the
+/// latest checkpoint one block earlier and nothing raised or released yet.
+#[cfg(test)]
+impl AuctionState {
+    pub fn for_test(params: &AuctionParams, block: u64, clearing_price: U256) -> Self {
+        Self {
+            block,
+            clearing_price,
+            currency_raised: U256::ZERO,
+            total_supply: 1_000_000,
+            checkpoint: CheckpointInfo {
+                block: block.saturating_sub(1),
+                clearing_price,
+                cumulative_mps: 0,
+            },
+            allocations: params.allocations.clone(),
+        }
+    }
+}
+
+/// Auction values that change block to block, unlike [`AuctionParams`].
+#[derive(Debug, Clone)]
+pub struct AuctionState {
+    pub block: u64,
+    pub clearing_price: U256,
+    pub currency_raised: U256,
+    /// Tokens on sale over the whole auction.
+    pub total_supply: u128,
+    pub checkpoint: CheckpointInfo,
+    /// Validation hook state for every sender with a pending bid.
+    pub allocations: Vec<SenderAllocation>,
+}
+
+/// The CCA's most recent checkpoint.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckpointInfo {
+    pub block: u64,
+    pub clearing_price: U256,
+    /// Share of the supply released so far, in milli-basis points.
+    pub cumulative_mps: u32,
+}
+
+/// Where our bids sit relative to the clearing price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BidStanding {
+    /// Strictly above the clearing price, so still filling.
+    pub above: usize,
+    /// At or below the clearing price.
+    pub at_or_below: usize,
+}
+
+impl AuctionState {
+    /// Tokens released up to the latest checkpoint.
+    pub fn released_supply(&self) -> U256 {
+        U256::from(self.total_supply) * U256::from(self.checkpoint.cumulative_mps) / U256::from(MPS)
+    }
+
+    pub fn standing(&self, max_bids: impl IntoIterator<Item = U256>) -> BidStanding {
+        max_bids
+            .into_iter()
+            .fold(BidStanding::default(), |mut standing, max_bid| {
+                if max_bid > self.clearing_price {
+                    standing.above += 1;
+                } else {
+                    standing.at_or_below += 1;
+                }
+                standing
+            })
+    }
+
+    pub fn allocation(&self, sender: Address) -> Option<&SenderAllocation> {
+        self.allocations
+            .iter()
+            .find(|allocation| allocation.sender == sender)
+    }
+}
+
+/// A sender's purchases so far and whether it holds the soulbound token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SenderAllocation {
+    pub sender: Address,
     pub total_purchased: U256,
     pub has_any_token: bool,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct AuctionWindow {
+    pub start_block: U256,
+    pub contributor_period_end_block: U256,
+    pub end_block: U256,
+    pub claim_block: U256,
+}
+
+impl AuctionWindow {
+    pub fn phase(&self, block_number: U256) -> AuctionPhase {
+        if block_number < self.start_block {
+            AuctionPhase::NotStarted
+        } else if block_number < self.contributor_period_end_block {
+            AuctionPhase::ContributorTrack
+        } else if block_number < self.end_block {
+            AuctionPhase::PublicTrack
+        } else if block_number < self.claim_block {
+            AuctionPhase::Ended
+        } else {
+            AuctionPhase::Claimable
+        }
+    }
+}
+
+/// The block at which the auction moved into `to`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PhaseTransition {
+    pub block: u64,
+    pub from: Option<AuctionPhase>,
+    pub to: AuctionPhase,
+}
+
 #[derive(Debug)]
 pub struct SubmitBidParams {
     pub max_price: U256,
     pub amount: u128,
     pub owner: Address,
     pub prev_tick_price: U256,
+    /// Use the `submitBid` overload that walks the tick list on chain.
+    pub hintless: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Auction, AuctionParams, AuctionPhase, AuctionState, AuctionWindow, BidStanding,
+        MAX_TICK_PTR,
+    };
+    use crate::CCA;
+    use alloy::{
+        primitives::{Address, Bytes, U256},
+        providers::{Provider, ProviderBuilder, bindings::IMulticall3, mock::Asserter},
+        sol_types::{SolCall, SolValue},
+    };
+
+    const FLOOR: u64 = 100;
+    const TICKS: [u64; 3] = [200, 300, 400];
+
+    fn auction(asserter: &Asserter) -> Auction<impl Provider + Clone> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        Auction::for_test(provider, Address::repeat_byte(0x01))
+    }
+
+    fn params() -> AuctionParams {
+        AuctionParams::for_test().with_ticks(U256::from(FLOOR), U256::from(FLOOR))
+    }
+
+    fn prices(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|value| U256::from(*value)).collect()
+    }
+
+    /// `next` pointer of `price` in the floor → 200 → 300 → 400 list.
+    fn next_of(price: u64) -> U256 {
+        let mut list = std::iter::once(FLOOR).chain(TICKS);
+        list.position(|tick| tick == price)
+            .and_then(|position| TICKS.get(position).copied())
+            .map_or(MAX_TICK_PTR, U256::from)
+    }
+
+    fn tick(price: u64) -> Bytes {
+        CCA::ticksCall::abi_encode_returns(&CCA::Tick {
+            next: next_of(price),
+            currencyDemandQ96: U256::ZERO,
+        })
+        .into()
+    }
+
+    /// Queues the `ticks()` responses of a walk from the floor up to `price`.
+    fn push_walk(asserter: &Asserter, price: u64) -> usize {
+        let mut calls = 0;
+        let mut current = FLOOR;
+        loop {
+            asserter.push_success(&tick(current));
+            calls += 1;
+            let next = next_of(current);
+            if next >= U256::from(price) || next == MAX_TICK_PTR {
+                return calls;
+            }
+            current = next.to::<u64>();
+        }
+    }
+
+    /// The hint lookup the batch pass replaced: a walk from the floor per bid.
+    async fn legacy_prev_tick<P: Provider + Clone>(auction: &Auction<P>, bid_price: U256) -> U256 {
+        let mut prev = U256::from(FLOOR);
+        let mut next = auction.cca.ticks(prev).call().await.unwrap().next;
+        while next < bid_price {
+            prev = next;
+            next = auction.cca.ticks(prev).call().await.unwrap().next;
+        }
+        prev
+    }
+
+    #[tokio::test]
+    async fn batch_walk_matches_per_bid_walks_in_fewer_calls() {
+        let bids = prices(&[450, 250, 300, 100, 350, 250]);
+
+        let asserter = Asserter::new();
+        let auction = auction(&asserter);
+        let mut legacy = Vec::new();
+        let mut per_bid_calls = 0;
+        for bid in &bids {
+            per_bid_calls += push_walk(&asserter, bid.to::<u64>());
+            legacy.push(legacy_prev_tick(&auction, *bid).await);
+        }
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(legacy, prices(&[400, 200, 200, 100, 300, 200]));
+
+        // One pass: floor, 200, 300, 400.
+        let batch_calls = push_walk(&asserter, 450);
+        let hints = auction
+            .prev_tick_prices(params().floor_price, &bids)
+            .await
+            .unwrap();
+        assert_eq!(hints, legacy);
+        assert!(asserter.read_q().is_empty());
+
+        assert_eq!(batch_calls, 4);
+        assert_eq!(per_bid_calls, 14);
+    }
+
+    #[tokio::test]
+    async fn cached_hints_are_verified_in_one_multicall() {
+        let bids = prices(&[450, 250, 350, 260]);
+        let asserter = Asserter::new();
+        let auction = auction(&asserter);
+        auction
+            .ticks_write()
+            .load(prices(&[FLOOR, 200, 300, 400]), 10);
+
+        let return_data = [200, 300, 400].into_iter().map(tick).collect();
+        asserter.push_success(&Bytes::from(
+            IMulticall3::aggregateCall::abi_encode_returns(&IMulticall3::aggregateReturn {
+                blockNumber: U256::from(10u64),
+                returnData: return_data,
+            }),
+        ));
+        let hints = auction
+            .prev_tick_prices(params().floor_price, &bids)
+            .await
+            .unwrap();
+        assert_eq!(hints, prices(&[400, 200, 300, 200]));
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_a_hook_without_a_soulbound_token() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        let hook = Address::repeat_byte(0x02);
+        let return_data = vec![
+            hook.abi_encode().into(),
+            Address::repeat_byte(0x03).abi_encode().into(),
+            Address::ZERO.abi_encode().into(),
+        ];
+        asserter.push_success(&Bytes::from(
+            IMulticall3::aggregateCall::abi_encode_returns(&IMulticall3::aggregateReturn {
+                blockNumber: U256::from(10u64),
+                returnData: return_data,
+            }),
+        ));
+        asserter.push_success(&Bytes::from(Address::ZERO.abi_encode()));
+
+        let err = Auction::new(provider, Address::repeat_byte(0x01))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "validation hook {hook} of auction {} has no soulbound token",
+            Address::repeat_byte(0x01)
+        )));
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[test]
+    fn phases_follow_the_auction_blocks() {
+        let window = AuctionWindow {
+            start_block: U256::from(100u64),
+            contributor_period_end_block: U256::from(200u64),
+            end_block: U256::from(300u64),
+            claim_block: U256::from(350u64),
+        };
+        let phase = |block: u64| window.phase(U256::from(block));
+        assert_eq!(phase(99), AuctionPhase::NotStarted);
+        assert_eq!(phase(100), AuctionPhase::ContributorTrack);
+        assert_eq!(phase(200), AuctionPhase::PublicTrack);
+        assert_eq!(phase(299), AuctionPhase::PublicTrack);
+        assert_eq!(phase(300), AuctionPhase::Ended);
+        assert_eq!(phase(350), AuctionPhase::Claimable);
+        assert!(!phase(299).is_closed());
+        assert!(phase(350).is_closed());
+    }
+
+    #[test]
+    fn compares_bids_with_the_clearing_price() {
+        let mut state = AuctionState::for_test(&params(), 250, U256::from(300u64));
+        state.checkpoint.cumulative_mps = 2_500_000;
+        assert_eq!(state.released_supply(), U256::from(250_000u64));
+        assert_eq!(
+            state.standing(prices(&[200, 300, 301, 400])),
+            BidStanding {
+                above: 2,
+                at_or_below: 2,
+            }
+        );
+    }
 }
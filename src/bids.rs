@@ -1,70 +1,166 @@
-use crate::{auction::AuctionParams, config::BidParams, registry::PlannedBid};
+use crate::{
+    auction::AuctionParams,
+    config::{BidParams, BidTemplate, TxSettings},
+    ladder::LadderTemplate,
+    registry::PlannedBid,
+};
 use alloy::primitives::U256;
-use tracing::warn;
+use eyre::{Result, eyre};
+use serde::Deserialize;
+use std::{collections::HashSet, fmt};
+use tracing::{info, warn};
 
-pub fn align_price_to_tick(price: U256, params: &AuctionParams) -> U256 {
+/// Direction a price that falls between ticks is moved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TickRounding {
+    /// Never raise a bid above the price the user asked for.
+    #[default]
+    Down,
+    Up,
+    Nearest,
+}
+
+impl fmt::Display for TickRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Down => "down",
+            Self::Up => "up",
+            Self::Nearest => "nearest",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Moves `price` onto the tick grid. A price below the floor is only raised to
+/// it when `rounding` allows moving up.
+pub fn align_price_to_tick(
+    price: U256,
+    params: &AuctionParams,
+    rounding: TickRounding,
+) -> Result<U256> {
     let floor = params.floor_price;
     let spacing = params.tick_spacing;
     let cap = params.max_bid_price;
 
+    if spacing.is_zero() {
+        return Err(eyre!(
+            "auction tick spacing is zero, so price {price} cannot be placed on a tick"
+        ));
+    }
+
     if price >= cap {
-        return cap;
+        return Ok(cap);
     }
 
-    if price <= floor {
-        return floor;
+    if price < floor {
+        return match rounding {
+            TickRounding::Down => Err(eyre!(
+                "price {price} is below the floor price {floor}, and tick_rounding = \"down\" never raises a bid"
+            )),
+            TickRounding::Up | TickRounding::Nearest => Ok(floor),
+        };
     }
 
     let offset = price - floor;
     let rem = offset % spacing;
     if rem.is_zero() {
-        return price.min(cap);
+        return Ok(price);
     }
 
     let down = price - rem;
     let up = down + spacing;
-    let choose_up = rem > spacing - rem;
-    let candidate = if choose_up { up } else { down };
-    candidate.min(cap)
+    let candidate = match rounding {
+        TickRounding::Down => down,
+        TickRounding::Up => up,
+        TickRounding::Nearest if rem > spacing - rem => up,
+        TickRounding::Nearest => down,
+    };
+    Ok(candidate.min(cap))
+}
+
+/// Resolves explicit bids and expands ladders into concrete, tick-aligned bids.
+pub fn resolve_bids(
+    templates: &[BidTemplate],
+    ladders: &[LadderTemplate],
+    params: &AuctionParams,
+) -> Result<Vec<BidParams>> {
+    let mut bids = templates
+        .iter()
+        .enumerate()
+        .map(|(idx, template)| template.resolve(idx, params))
+        .collect::<Result<Vec<_>>>()?;
+
+    for ladder in ladders {
+        let levels = ladder.expand(params)?;
+        info!(
+            ladder = %ladder.label,
+            levels = levels.len(),
+            "ladder expanded"
+        );
+        bids.extend(levels);
+    }
+
+    let mut labels = HashSet::new();
+    for bid in &bids {
+        if !labels.insert(bid.label.as_str()) {
+            return Err(eyre!("bid label {} is used more than once", bid.label));
+        }
+    }
+
+    Ok(bids)
 }
 
-pub fn preprocess_bids(bids: &[BidParams], params: &AuctionParams) -> Vec<PlannedBid> {
+pub fn preprocess_bids(bids: &[BidParams]) -> Vec<PlannedBid> {
     bids.iter()
         .cloned()
-        .map(|mut bid| {
-            let aligned = align_price_to_tick(bid.max_bid, params);
-            if aligned != bid.max_bid {
+        .map(|bid| {
+            if bid.max_bid != bid.requested_max_bid {
                 warn!(
+                    label = %bid.label,
                     owner = ?bid.owner,
-                    original = %bid.max_bid,
-                    adjusted = %aligned,
-                    "max bid adjusted to nearest tick"
+                    rounding = %bid.rounding,
+                    original = %bid.requested_max_bid,
+                    adjusted = %bid.max_bid,
+                    "max bid adjusted to tick"
                 );
-                bid.max_bid = aligned;
             }
-            PlannedBid::new(bid)
+            let TxSettings {
+                tx_config,
+                max_retries,
+                ..
+            } = bid.tx.clone();
+            let mut planned = PlannedBid::new(bid);
+            if let Some(tx_config) = tx_config {
+                planned = planned.with_tx_config(tx_config);
+            }
+            if let Some(max_retries) = max_retries {
+                planned = planned.with_max_retries(max_retries);
+            }
+            planned
         })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::align_price_to_tick;
-    use crate::auction::AuctionParams;
-    use alloy::primitives::U256;
+    use super::{TickRounding, align_price_to_tick, resolve_bids};
+    use crate::{
+        auction::AuctionParams,
+        config::{BidPrice, BidTemplate, TxSettings},
+        schedule::WindowSpec,
+        units::{AmountSpec, AmountUnit},
+    };
+    use alloy::primitives::{Address, U256};
 
     fn params() -> AuctionParams {
         use std::str::FromStr;
-        AuctionParams {
-            contributor_period_end_block: U256::ZERO,
-            max_purchase_limit: U256::ZERO,
-            floor_price: U256::from_str("753956294022871543408300").unwrap(),
-            tick_spacing: U256::from_str("7539562940228715434083").unwrap(),
-            max_bid_price: U256::from_str("217900404829510685459725614601655060836").unwrap(),
-            end_block: U256::ZERO,
-            total_purchased: U256::ZERO,
-            has_any_token: true,
-        }
+        AuctionParams::for_test()
+            .with_ticks(
+                U256::from_str("753956294022871543408300").unwrap(),
+                U256::from_str("7539562940228715434083").unwrap(),
+            )
+            .with_max_bid_price(U256::from_str("217900404829510685459725614601655060836").unwrap())
     }
 
     #[test]
@@ -77,7 +173,12 @@ mod tests {
             U256::from_str("1839653357415806565916252").unwrap(),
         ];
         for price in aligned {
-            assert_eq!(align_price_to_tick(price, &params), price);
+            for rounding in [TickRounding::Down, TickRounding::Up, TickRounding::Nearest] {
+                assert_eq!(
+                    align_price_to_tick(price, &params, rounding).unwrap(),
+                    price
+                );
+            }
         }
     }
 
@@ -88,13 +189,73 @@ mod tests {
         let floor = params.floor_price;
         let price = floor + spacing * U256::from(10) + spacing / U256::from(3);
         let expected = floor + spacing * U256::from(10);
-        assert_eq!(align_price_to_tick(price, &params), expected);
+        assert_eq!(
+            align_price_to_tick(price, &params, TickRounding::Nearest).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn rounds_in_requested_direction() {
+        let params = params();
+        let spacing = params.tick_spacing;
+        let floor = params.floor_price;
+        let below = floor + spacing * U256::from(10);
+        let price = below + spacing * U256::from(2) / U256::from(3);
+        assert_eq!(
+            align_price_to_tick(price, &params, TickRounding::Down).unwrap(),
+            below
+        );
+        assert_eq!(
+            align_price_to_tick(price, &params, TickRounding::Up).unwrap(),
+            below + spacing
+        );
+        assert_eq!(
+            align_price_to_tick(price, &params, TickRounding::Nearest).unwrap(),
+            below + spacing
+        );
     }
 
     #[test]
     fn clamps_above_cap() {
         let params = params();
         let price = params.max_bid_price + U256::from(1);
-        assert_eq!(align_price_to_tick(price, &params), params.max_bid_price);
+        assert_eq!(
+            align_price_to_tick(price, &params, TickRounding::Up).unwrap(),
+            params.max_bid_price
+        );
+    }
+
+    #[test]
+    fn only_raises_below_floor_prices_when_rounding_up() {
+        let params = params();
+        let price = params.floor_price - U256::from(1);
+        assert!(align_price_to_tick(price, &params, TickRounding::Down).is_err());
+        for rounding in [TickRounding::Up, TickRounding::Nearest] {
+            assert_eq!(
+                align_price_to_tick(price, &params, rounding).unwrap(),
+                params.floor_price
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_zero_tick_spacing() {
+        let params = params().with_ticks(U256::from(1_000u64), U256::ZERO);
+        assert!(align_price_to_tick(U256::from(1_500u64), &params, TickRounding::Down).is_err());
+
+        let bid = BidTemplate {
+            label: "bid-1".to_string(),
+            price: BidPrice::Q96(U256::from(1_500u64)),
+            amount: "1000".parse::<AmountSpec>().unwrap(),
+            amount_unit: AmountUnit::Wei,
+            owner: Address::ZERO,
+            sender: Address::ZERO,
+            tx: TxSettings::default(),
+            window: WindowSpec::default(),
+            rounding: TickRounding::Down,
+        };
+        let err = resolve_bids(&[bid], &[], &params).unwrap_err();
+        assert!(err.to_string().contains("tick spacing is zero"));
     }
 }
@@ -1,32 +1,46 @@
 use crate::{
-    auction::{Auction, AuctionParams, SubmitBidParams},
+    CCA,
+    auction::{Auction, AuctionPhase, AuctionWindow, SubmitBidParams},
     config::BidParams,
+    failure::{BidFailure, RevertData, UnconfirmedBid, is_stale_hint, unconfirmed_tx},
     registry::{BidRegistry, BidSummary, RetryStatus, TrackedBid},
+    signer::BidSigner,
     transaction::{TxBuilder, TxConfig},
 };
 use std::{
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use alloy::{
+    eips::BlockId,
     network::BlockResponse,
-    primitives::{Address, B256, U256},
-    providers::Provider,
+    primitives::{Address, B256, Bytes, U256},
+    providers::{Provider, ext::DebugApi},
     rpc::{
         client::BuiltInConnectionString,
-        types::{TransactionRequest, eth::Header},
+        types::{
+            TransactionReceipt, TransactionRequest,
+            eth::Header,
+            trace::geth::{CallConfig, GethDebugTracingOptions},
+        },
     },
-    signers::local::PrivateKeySigner,
 };
 use eyre::{Result, eyre};
 use futures_util::{Stream, StreamExt, stream::BoxStream};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 use tracing::{error, info, info_span, instrument, warn};
 
+/// Stale-hint resends per attempt before the revert counts as a failure.
+const MAX_HINT_RECOVERIES: u8 = 2;
+
+/// Delay between receipt lookups while a bid transaction is pending.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct BlockProducer<P>
 where
     P: Provider + Clone + Unpin,
@@ -89,11 +103,18 @@ where
     P: Provider + Clone,
 {
     auction: Auction<P>,
-    params: AuctionParams,
+    /// Only the static floor: live auction values are read into `AuctionState`
+    /// by the registry each block.
+    floor_price: U256,
     bid_params: BidParams,
-    signer: PrivateKeySigner,
+    signer: Arc<dyn BidSigner>,
     tx_config: Option<TxConfig>,
     cca_addr: Address,
+    /// Hint resolved for this block by the registry's batch pass.
+    prev_tick_hint: Option<U256>,
+    /// Set once a recomputed hint was rejected and `hint_fallback` allows it.
+    /// Scoped to one attempt: the next attempt starts with a hint again.
+    hintless: bool,
 }
 
 impl<P> BidContext<P>
@@ -102,36 +123,71 @@ where
 {
     pub fn new(
         auction: Auction<P>,
-        params: AuctionParams,
+        floor_price: U256,
         bid_params: BidParams,
-        signer: PrivateKeySigner,
+        signer: Arc<dyn BidSigner>,
         tx_config: Option<TxConfig>,
         cca_addr: Address,
     ) -> Self {
         Self {
             auction,
-            params,
+            floor_price,
             bid_params,
             signer,
             tx_config,
             cca_addr,
+            prev_tick_hint: None,
+            hintless: false,
         }
     }
 
+    pub fn set_prev_tick_hint(&mut self, hint: Option<U256>) {
+        self.prev_tick_hint = hint;
+    }
+
     pub async fn prepare_submit_bid(&self) -> Result<SubmitBidParams> {
-        self.auction
-            .prepare_submit_bid(&self.bid_params, &self.params, self.bid_params.owner)
-            .await
+        let mut submit = self
+            .auction
+            .prepare_submit_bid(
+                &self.bid_params,
+                self.floor_price,
+                self.bid_params.owner,
+                self.prev_tick_hint,
+            )
+            .await?;
+        submit.hintless = self.hintless;
+        Ok(submit)
     }
 
-    pub async fn build_transaction(&self, submit: &SubmitBidParams) -> Result<TransactionRequest> {
-        let builder = TxBuilder::new(
+    /// Handles the CCA rejecting our `prevTickPrice`: the first time the hint is
+    /// recomputed against the latest tick list, after that the bid switches to
+    /// the hintless `submitBid` if `hint_fallback` is set.
+    async fn recover_stale_hint(&mut self, recoveries: u8) -> Result<()> {
+        if recoveries > 1 && self.bid_params.tx.hint_fallback {
+            warn!("recomputed hint rejected again, falling back to hintless submitBid");
+            self.hintless = true;
+            return Ok(());
+        }
+        let hints = self
+            .auction
+            .prev_tick_prices(self.floor_price, &[self.bid_params.max_bid])
+            .await?;
+        warn!(prev_tick_price = %hints[0], "prevTickPrice hint went stale, recomputed");
+        self.prev_tick_hint = Some(hints[0]);
+        Ok(())
+    }
+
+    fn tx_builder(&self) -> TxBuilder<P> {
+        TxBuilder::new(
             self.auction.provider.clone(),
             self.signer.clone(),
             self.cca_addr,
             self.tx_config.clone(),
-        );
-        builder.build_submit_bid_request(submit).await
+        )
+    }
+
+    pub async fn build_transaction(&self, submit: &SubmitBidParams) -> Result<TransactionRequest> {
+        self.tx_builder().build_submit_bid_request(submit).await
     }
 
     pub async fn simulate_transaction(&self, tx: &TransactionRequest) -> Result<()> {
@@ -139,14 +195,77 @@ where
         Ok(())
     }
 
-    pub async fn send_transaction(&self, tx: TransactionRequest) -> Result<B256> {
-        let pending = self.auction.provider.send_transaction(tx).await?;
-        let receipt = pending.get_receipt().await?;
-        info!(tx = ?receipt.transaction_hash, "bid submitted");
-        Ok(receipt.transaction_hash)
+    pub fn bid_calldata(&self, submit: &SubmitBidParams) -> Bytes {
+        self.tx_builder().bid_calldata(submit)
+    }
+
+    pub async fn sign_transaction(&self, tx: TransactionRequest) -> Result<Bytes> {
+        self.tx_builder().sign(tx).await
+    }
+
+    pub async fn send_transaction(&self, tx: TransactionRequest) -> Result<Submission> {
+        let raw = self.sign_transaction(tx.clone()).await?;
+        let pending = self.auction.provider.send_raw_transaction(&raw).await?;
+        let receipt = wait_for_receipt(&self.auction.provider, *pending.tx_hash()).await?;
+        let bid = check_receipt(&self.auction.provider, &receipt, self.cca_addr, tx).await?;
+        info!(
+            tx = ?receipt.transaction_hash,
+            bid_id = %bid.id,
+            price = %bid.price,
+            amount = bid.amount,
+            "bid accepted"
+        );
+        Ok(Submission {
+            tx_hash: receipt.transaction_hash,
+            bid_id: bid.id,
+            mismatch: None,
+        })
+    }
+
+    /// Reads the bid back from the CCA and checks it holds the max price,
+    /// amount and owner this bid was planned with. Only the integer part of
+    /// `amountQ96` is compared, so fractional bits the CCA may keep do not
+    /// read as a mismatch.
+    pub async fn confirm_bid(&self, bid_id: U256) -> Result<()> {
+        let bid = self.auction.cca.bids(bid_id).call().await?;
+        let planned = &self.bid_params;
+
+        let mut mismatches = Vec::new();
+        if bid.maxPrice != planned.max_bid {
+            mismatches.push(format!(
+                "max price {} (planned {})",
+                bid.maxPrice, planned.max_bid
+            ));
+        }
+        let amount = bid.amountQ96 >> 96;
+        if amount != U256::from(planned.amount) {
+            mismatches.push(format!("amount {amount} (planned {})", planned.amount));
+        }
+        if bid.owner != planned.owner {
+            mismatches.push(format!("owner {} (planned {})", bid.owner, planned.owner));
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!(
+                "bid {bid_id} on chain differs from the plan: {}",
+                mismatches.join(", ")
+            ))
+        }
     }
 }
 
+/// A bid the auction accepted.
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub tx_hash: B256,
+    /// ID returned by `submitBid`, taken from the `BidSubmitted` event.
+    pub bid_id: U256,
+    /// Why the bid read back from the CCA could not be confirmed against the plan.
+    pub mismatch: Option<String>,
+}
+
 pub struct BlockConsumer<P>
 where
     P: Provider + Clone,
@@ -170,20 +289,79 @@ where
         !self.registry.all_done()
     }
 
+    /// Per-block status line: the clearing price against our live bids, plus
+    /// raise and supply release progress.
+    fn log_status(&self, phase: AuctionPhase, window: &AuctionWindow, block_number: U256) {
+        let next_boundary = match phase {
+            AuctionPhase::ContributorTrack => window.contributor_period_end_block,
+            _ => window.end_block,
+        };
+        let blocks_remaining = next_boundary.saturating_sub(block_number);
+        let Some(state) = self.registry.state() else {
+            info!(phase = %phase, blocks_remaining = %blocks_remaining, "auction state unavailable");
+            return;
+        };
+
+        let params = self.registry.params();
+        let standing = state.standing(self.registry.live_max_bids());
+        info!(
+            phase = %phase,
+            blocks_remaining = %blocks_remaining,
+            clearing_price = %params.price_scale.format_q96(state.clearing_price),
+            currency_raised = %params.currency.format_amount(state.currency_raised),
+            supply_released = %format!(
+                "{}/{}",
+                params.price_scale.format_tokens(state.released_supply()),
+                params.price_scale.format_tokens(U256::from(state.total_supply))
+            ),
+            state_block = state.block,
+            checkpoint_block = state.checkpoint.block,
+            bids_above_clearing = standing.above,
+            bids_at_or_below_clearing = standing.at_or_below,
+            "auction status"
+        );
+    }
+
     #[instrument(skip_all, fields(block = header.number))]
     pub async fn handle_block(&mut self, header: &Header) -> Result<Completion> {
-        let window = self.registry.window();
+        let window = *self.registry.window();
         let block_number = U256::from(header.number);
+        let phase = window.phase(block_number);
+        if let Some(transition) = self.registry.observe_phase(header.number, phase) {
+            info!(phase = %transition.to, previous = ?transition.from, "auction phase changed");
+        }
 
-        if block_number < window.contributor_period_end_block {
+        if phase == AuctionPhase::NotStarted {
             info!(
-                blocks_remaining = %window.contributor_period_end_block - block_number,
-                "contributor track active"
+                blocks_remaining = %window.start_block - block_number,
+                "auction not started"
             );
             return Ok(Completion::Pending);
         }
 
-        if block_number >= window.end_block {
+        match self.registry.refresh_state(header.number).await {
+            Ok(closed) => {
+                for (label, reason) in closed {
+                    warn!(label = %label, reason = %reason, "pending bid no longer valid, closing it");
+                }
+            }
+            Err(err) => {
+                warn!(error = ?err, "auction state refresh failed, keeping the previous state")
+            }
+        }
+        self.log_status(phase, &window, block_number);
+        if self.registry.all_done() {
+            return Ok(Completion::Finished {
+                summary: self.registry.summary(),
+                reason: ShutdownReason::AllBidsProcessed,
+            });
+        }
+
+        if phase == AuctionPhase::ContributorTrack {
+            return Ok(Completion::Pending);
+        }
+
+        if phase.is_closed() {
             let summary = self.registry.summary();
             let pending = summary.pending;
             if pending > 0 {
@@ -205,12 +383,36 @@ where
             });
         }
 
+        if let Err(err) = self.registry.sync_ticks(header.number).await {
+            warn!(error = ?err, "tick cache sync failed, hints will reload the tick list");
+        }
+        if let Err(err) = self.registry.resolve_hints(Some(block_number)).await {
+            warn!(error = ?err, "batch hint resolution failed, resolving hints per bid");
+        }
+
         for tracked in self.registry.bids_mut().iter_mut() {
             if !tracked.is_pending() {
                 continue;
             }
 
+            let bid_window = tracked.bid_params().window;
+            if bid_window.is_closed(block_number) {
+                warn!(
+                    label = %tracked.bid_params().label,
+                    owner = ?tracked.bid_params().owner,
+                    not_after_block = ?bid_window.not_after,
+                    attempts = tracked.attempts(),
+                    "bid window closed before submission"
+                );
+                tracked.mark_expired(block_number);
+                continue;
+            }
+            if !bid_window.is_open(block_number) {
+                continue;
+            }
+
             info!(
+                label = %tracked.bid_params().label,
                 owner = ?tracked.bid_params().owner,
                 amount = tracked.bid_params().amount,
                 attempt = tracked.attempts() + 1,
@@ -218,25 +420,7 @@ where
                 "submitting bid"
             );
 
-            match submit_bid(tracked).await {
-                Ok(tx_hash) => tracked.mark_submitted(tx_hash),
-                Err(err) => match tracked.record_failure(format!("{err:?}")) {
-                    RetryStatus::Retrying(attempts) => warn!(
-                        owner = ?tracked.bid_params().owner,
-                        attempts,
-                        max_retries = tracked.max_retries(),
-                        error = ?err,
-                        "bid retry scheduled"
-                    ),
-                    RetryStatus::Exhausted => error!(
-                        owner = ?tracked.bid_params().owner,
-                        attempts = tracked.attempts(),
-                        max_retries = tracked.max_retries(),
-                        error = ?err,
-                        "bid failed permanently"
-                    ),
-                },
-            }
+            attempt_bid(tracked).await;
         }
 
         if self.registry.all_done() {
@@ -260,7 +444,7 @@ pub enum Completion {
     },
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ShutdownReason {
     AllBidsProcessed,
     AuctionEndedWithPending,
@@ -270,26 +454,215 @@ pub enum ShutdownReason {
     BlockStreamEndedWithPending,
 }
 
-async fn submit_bid<P>(tracked: &mut TrackedBid<P>) -> Result<B256>
+/// Submits a pending bid once and records the outcome on it.
+async fn attempt_bid<P>(tracked: &mut TrackedBid<P>)
+where
+    P: Provider + Clone,
+{
+    match submit_bid(tracked).await {
+        Ok(submission) => {
+            tracked.mark_submitted(submission.tx_hash, submission.bid_id, submission.mismatch)
+        }
+        Err(err) => {
+            // A mined, successful transaction spent its nonce and may hold a
+            // bid, so it is recorded as included rather than retried.
+            if let Some(tx_hash) = unconfirmed_tx(&err) {
+                error!(
+                    label = %tracked.bid_params().label,
+                    owner = ?tracked.bid_params().owner,
+                    tx = ?tx_hash,
+                    error = ?err,
+                    "bid transaction included without a BidSubmitted event, not resending"
+                );
+                tracked.mark_unconfirmed(tx_hash, format!("{err:#}"));
+                return;
+            }
+            let failure = BidFailure::from_report(&err);
+            match tracked.record_failure(failure.clone()) {
+                RetryStatus::Retrying(attempts) => warn!(
+                    label = %tracked.bid_params().label,
+                    owner = ?tracked.bid_params().owner,
+                    attempts,
+                    max_retries = tracked.max_retries(),
+                    failure = %failure,
+                    error = ?err,
+                    "bid retry scheduled"
+                ),
+                RetryStatus::Exhausted => error!(
+                    label = %tracked.bid_params().label,
+                    owner = ?tracked.bid_params().owner,
+                    attempts = tracked.attempts(),
+                    max_retries = tracked.max_retries(),
+                    failure = %failure,
+                    error = ?err,
+                    "bid failed permanently"
+                ),
+                RetryStatus::Fatal => error!(
+                    label = %tracked.bid_params().label,
+                    owner = ?tracked.bid_params().owner,
+                    attempts = tracked.attempts(),
+                    failure = %failure,
+                    error = ?err,
+                    "bid rejected by the auction, not retrying"
+                ),
+            }
+        }
+    }
+}
+
+async fn submit_bid<P>(tracked: &mut TrackedBid<P>) -> Result<Submission>
 where
     P: Provider + Clone,
 {
     let span = info_span!(
         "bid",
+        label = %tracked.bid_params().label,
         owner = ?tracked.bid_params().owner,
+        sender = ?tracked.bid_params().sender,
         amount = tracked.bid_params().amount,
         attempt = tracked.attempts() + 1
     );
     let _enter = span.enter();
 
+    // Stale hints are someone else's tick landing first, not a strategy
+    // failure, so they are resent here without spending a retry.
     let context = tracked.context_mut();
+    context.hintless = false;
+    let mut recoveries = 0;
+    loop {
+        let result = match prepare_and_simulate(context).await {
+            Ok(tx_request) => context.send_transaction(tx_request).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Err(err) if is_stale_hint(&err) && recoveries < MAX_HINT_RECOVERIES => {
+                recoveries += 1;
+                context.recover_stale_hint(recoveries).await?;
+            }
+            Ok(mut submission) => {
+                // The bid is on chain either way, so a mismatch is recorded
+                // for the summary rather than retried.
+                match context.confirm_bid(submission.bid_id).await {
+                    Ok(()) => info!(bid_id = %submission.bid_id, "bid confirmed on chain"),
+                    Err(err) => {
+                        error!(bid_id = %submission.bid_id, error = ?err, "bid could not be confirmed");
+                        submission.mismatch = Some(format!("{err:#}"));
+                    }
+                }
+                return Ok(submission);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Confirms the auction accepted an included bid: the receipt succeeded and
+/// carries the CCA's `BidSubmitted` event. The revert reason of a reverted
+/// transaction is taken from its `debug_traceTransaction` call frame, or, on
+/// nodes without the debug API, by replaying it with `eth_call` at the
+/// inclusion block. A reverted transaction leaves no state behind, so that
+/// block's state still holds whatever front-ran it there.
+pub async fn check_receipt<P>(
+    provider: &P,
+    receipt: &TransactionReceipt,
+    cca: Address,
+    replay: TransactionRequest,
+) -> Result<CCA::BidSubmitted>
+where
+    P: Provider,
+{
+    let tx_hash = receipt.transaction_hash;
+    if !receipt.status() {
+        let reason = match traced_revert(provider, tx_hash).await {
+            Some(data) => Some(eyre::Report::new(data)),
+            None => match receipt.block_number {
+                Some(block) => provider
+                    .call(replay)
+                    .block(BlockId::number(block))
+                    .await
+                    .err()
+                    .map(eyre::Report::new),
+                None => None,
+            },
+        };
+        return Err(match reason {
+            Some(err) => err.wrap_err(format!("bid transaction {tx_hash} reverted")),
+            None => eyre!("bid transaction {tx_hash} reverted"),
+        });
+    }
+
+    receipt
+        .inner
+        .logs()
+        .iter()
+        .filter(|log| log.address() == cca)
+        .find_map(|log| log.log_decode::<CCA::BidSubmitted>().ok())
+        .map(|log| log.inner.data)
+        .ok_or_else(|| eyre::Report::new(UnconfirmedBid(tx_hash)))
+}
+
+/// Polls for the receipt of `tx_hash` until the transaction is included.
+async fn wait_for_receipt<P>(provider: &P, tx_hash: B256) -> Result<TransactionReceipt>
+where
+    P: Provider,
+{
+    loop {
+        if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+            return Ok(receipt);
+        }
+        sleep(RECEIPT_POLL_INTERVAL).await;
+    }
+}
+
+/// Revert data of the transaction's top call frame, if the node traces it.
+async fn traced_revert<P>(provider: &P, tx_hash: B256) -> Option<RevertData>
+where
+    P: Provider,
+{
+    let options = GethDebugTracingOptions::call_tracer(CallConfig::default().only_top_call());
+    let frame = provider
+        .debug_trace_transaction(tx_hash, options)
+        .await
+        .ok()?
+        .try_into_call_frame()
+        .ok()?;
+    frame.error?;
+    frame
+        .output
+        .filter(|output| !output.is_empty())
+        .map(RevertData)
+}
+
+/// Runs prepare → build → simulate for a bid without broadcasting it.
+pub async fn simulate_bid<P>(tracked: &mut TrackedBid<P>) -> Result<()>
+where
+    P: Provider + Clone,
+{
+    let span = info_span!(
+        "bid",
+        label = %tracked.bid_params().label,
+        owner = ?tracked.bid_params().owner,
+        sender = ?tracked.bid_params().sender,
+        amount = tracked.bid_params().amount,
+        mode = "simulate"
+    );
+    let _enter = span.enter();
+
+    prepare_and_simulate(tracked.context_mut()).await?;
+    Ok(())
+}
+
+async fn prepare_and_simulate<P>(context: &BidContext<P>) -> Result<TransactionRequest>
+where
+    P: Provider + Clone,
+{
     let submit_bid_params = context.prepare_submit_bid().await?;
     info!("prepared submit params");
     let tx_request = context.build_transaction(&submit_bid_params).await?;
     info!("built transaction request");
     context.simulate_transaction(&tx_request).await?;
     info!("simulation succeeded");
-    context.send_transaction(tx_request).await
+    Ok(tx_request)
 }
 
 async fn align_polling<P>(provider: &P) -> Result<()>
@@ -307,3 +680,456 @@ where
     sleep(Duration::from_millis(500)).await;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{attempt_bid, check_receipt, submit_bid};
+    use crate::{
+        CCA,
+        auction::{Auction, AuctionParams},
+        bids::TickRounding,
+        config::{BidParams, TxSettings},
+        failure::{BidFailure, FailureClass, is_stale_hint},
+        registry::{BidOutcomeState, BidRegistry, PlannedBid},
+        schedule::BidWindow,
+        senders::Senders,
+        signer::{BidSigner, LocalSigner, SafeAccount},
+        transaction::TxConfig,
+    };
+    use alloy::{
+        primitives::{Address, B256, Bytes, LogData, U256},
+        providers::{Provider, ProviderBuilder, mock::Asserter},
+        rpc::types::{TransactionReceipt, TransactionRequest},
+        signers::local::PrivateKeySigner,
+        sol_types::{Revert, SolCall, SolError, SolEvent},
+    };
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const CCA_ADDR: Address = Address::repeat_byte(0x0c);
+    const SENDER: Address = Address::repeat_byte(0x0a);
+
+    /// One bid at 250 over a floor of 100 with ticks at 200 and 300, starting
+    /// from the stale hint 100.
+    fn registry(asserter: &Asserter, hint_fallback: bool) -> BidRegistry<impl Provider + Clone> {
+        registry_with(asserter, hint_fallback, Arc::new(SafeAccount::new(SENDER)))
+    }
+
+    /// Like `registry`, but sent from `signer` with fixed fees, so a bid can
+    /// be signed and broadcast against the mock.
+    fn registry_with(
+        asserter: &Asserter,
+        hint_fallback: bool,
+        signer: Arc<dyn BidSigner>,
+    ) -> BidRegistry<impl Provider + Clone> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        let params = AuctionParams::for_test().with_ticks(U256::from(100u64), U256::from(100u64));
+        let bid = BidParams {
+            label: "bid".to_string(),
+            max_bid: U256::from(250u64),
+            requested_max_bid: U256::from(250u64),
+            amount: 1_000,
+            owner: SENDER,
+            sender: signer.address(),
+            tx: TxSettings {
+                hint_fallback,
+                ..TxSettings::default()
+            },
+            window: BidWindow::default(),
+            rounding: TickRounding::Down,
+        };
+        let mut registry = BidRegistry::new(
+            Auction::for_test(provider, CCA_ADDR),
+            params,
+            vec![PlannedBid::new(bid).with_tx_config(TxConfig::new().with_fee_overrides(2, 1))],
+            &Senders::single(signer),
+            CCA_ADDR,
+        )
+        .unwrap();
+        registry.bids_mut()[0]
+            .context_mut()
+            .set_prev_tick_hint(Some(U256::from(100u64)));
+        registry
+    }
+
+    fn local_signer() -> Arc<dyn BidSigner> {
+        let key = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x11)).unwrap();
+        Arc::new(LocalSigner::new(key))
+    }
+
+    /// Queues a passing simulation, the signing lookups, the broadcast and
+    /// `receipt` as the transaction's receipt.
+    fn push_send(asserter: &Asserter, receipt: &TransactionReceipt) {
+        asserter.push_success(&Bytes::new());
+        asserter.push_success(&"0x0");
+        asserter.push_success(&"0x1");
+        asserter.push_success(&"0x927c0");
+        asserter.push_success(&B256::repeat_byte(0x01));
+        asserter.push_success(receipt);
+    }
+
+    fn push_revert(asserter: &Asserter, data: Vec<u8>) {
+        asserter.push_failure(
+            serde_json::from_value(json!({
+                "code": 3,
+                "message": "execution reverted",
+                "data": Bytes::from(data),
+            }))
+            .unwrap(),
+        );
+    }
+
+    /// Queues the tick walk floor → 200 → 300 that recomputes the hint to 200.
+    fn push_tick_walk(asserter: &Asserter) {
+        for next in [200u64, 300] {
+            asserter.push_success(&Bytes::from(CCA::ticksCall::abi_encode_returns(
+                &CCA::Tick {
+                    next: U256::from(next),
+                    currencyDemandQ96: U256::ZERO,
+                },
+            )));
+        }
+    }
+
+    fn receipt(status: bool, logs: &[LogData]) -> TransactionReceipt {
+        let logs: Vec<_> = logs
+            .iter()
+            .map(|data| {
+                json!({
+                    "address": CCA_ADDR,
+                    "topics": data.topics(),
+                    "data": data.data,
+                    "blockNumber": "0x10",
+                    "transactionHash": B256::repeat_byte(0x01),
+                    "transactionIndex": "0x0",
+                    "logIndex": "0x0",
+                    "removed": false,
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "type": "0x2",
+            "status": if status { "0x1" } else { "0x0" },
+            "cumulativeGasUsed": "0x5208",
+            "logs": logs,
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": B256::repeat_byte(0x01),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0x02),
+            "blockNumber": "0x10",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "from": Address::repeat_byte(0x0a),
+            "to": CCA_ADDR,
+            "contractAddress": null,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn accepts_receipts_with_a_bid_event() {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(Asserter::new());
+        let event = CCA::BidSubmitted {
+            id: U256::from(42u64),
+            owner: Address::repeat_byte(0x0b),
+            price: U256::from(300u64),
+            amount: 5,
+        };
+
+        let accepted = receipt(true, &[event.encode_log_data()]);
+        let bid = check_receipt(
+            &provider,
+            &accepted,
+            CCA_ADDR,
+            TransactionRequest::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(bid.id, U256::from(42u64));
+
+        let silent = receipt(true, &[]);
+        assert!(
+            check_receipt(&provider, &silent, CCA_ADDR, TransactionRequest::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn classifies_reverts_traced_from_the_inclusion_block() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        let reverted = receipt(false, &[]);
+        let push_frame = |data: Vec<u8>| {
+            asserter.push_success(&json!({
+                "from": SENDER,
+                "gas": "0x927c0",
+                "gasUsed": "0x5208",
+                "to": CCA_ADDR,
+                "input": "0x",
+                "output": Bytes::from(data),
+                "error": "execution reverted",
+                "type": "CALL",
+            }));
+        };
+
+        // A tick inserted earlier in the same block makes the hint stale.
+        push_frame(CCA::TickPreviousPriceInvalid {}.abi_encode());
+        let err = check_receipt(
+            &provider,
+            &reverted,
+            CCA_ADDR,
+            TransactionRequest::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(is_stale_hint(&err));
+        assert_eq!(BidFailure::from_report(&err).class, FailureClass::Hint);
+
+        push_frame(CCA::BidMustBeAboveClearingPrice {}.abi_encode());
+        let err = check_receipt(
+            &provider,
+            &reverted,
+            CCA_ADDR,
+            TransactionRequest::default(),
+        )
+        .await
+        .unwrap_err();
+        let failure = BidFailure::from_report(&err);
+        assert_eq!(failure.class, FailureClass::Fatal);
+        assert_eq!(failure.name.as_deref(), Some("BidMustBeAboveClearingPrice"));
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn replays_reverts_when_the_node_cannot_trace() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("the method debug_traceTransaction does not exist");
+        push_revert(&asserter, CCA::TickPreviousPriceInvalid {}.abi_encode());
+
+        let reverted = receipt(false, &[]);
+        let err = check_receipt(
+            &provider,
+            &reverted,
+            CCA_ADDR,
+            TransactionRequest::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(BidFailure::from_report(&err).class, FailureClass::Hint);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn included_bids_without_an_event_are_never_resent() {
+        let asserter = Asserter::new();
+        let mut registry = registry_with(&asserter, false, local_signer());
+        push_send(&asserter, &receipt(true, &[]));
+
+        attempt_bid(&mut registry.bids_mut()[0]).await;
+        assert!(asserter.read_q().is_empty());
+        let tracked = &registry.bids_mut()[0];
+        assert!(!tracked.is_pending());
+        assert_eq!(tracked.attempts(), 0);
+        assert!(registry.all_done());
+
+        let summary = registry.summary();
+        assert_eq!(summary.submitted, 1);
+        assert!(matches!(
+            &summary.outcomes[0].state,
+            BidOutcomeState::Unconfirmed { tx_hash, error }
+                if *tx_hash == B256::repeat_byte(0x01) && error.contains("no BidSubmitted event")
+        ));
+    }
+
+    #[tokio::test]
+    async fn stale_hint_recovery_does_not_spend_a_retry() {
+        let asserter = Asserter::new();
+        let mut registry = registry_with(&asserter, false, local_signer());
+
+        // An ordinary revert spends a retry.
+        push_revert(&asserter, Revert::from("paused").abi_encode());
+        attempt_bid(&mut registry.bids_mut()[0]).await;
+        assert_eq!(registry.bids_mut()[0].attempts(), 1);
+
+        // A stale hint is recomputed and the resend lands without spending one.
+        let event = CCA::BidSubmitted {
+            id: U256::from(7u64),
+            owner: SENDER,
+            price: U256::from(250u64),
+            amount: 1_000,
+        };
+        push_revert(&asserter, CCA::TickPreviousPriceInvalid {}.abi_encode());
+        push_tick_walk(&asserter);
+        push_send(&asserter, &receipt(true, &[event.encode_log_data()]));
+        asserter.push_success(&Bytes::from(CCA::bidsCall::abi_encode_returns(&CCA::Bid {
+            startBlock: 16,
+            startCumulativeMps: Default::default(),
+            exitedBlock: 0,
+            maxPrice: U256::from(250u64),
+            owner: SENDER,
+            amountQ96: U256::from(1_000u64) << 96,
+            tokensFilled: U256::ZERO,
+        })));
+
+        let tracked = &mut registry.bids_mut()[0];
+        attempt_bid(tracked).await;
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(tracked.attempts(), 1);
+        assert!(!tracked.is_pending());
+        assert_eq!(
+            tracked.context_mut().prev_tick_hint,
+            Some(U256::from(200u64))
+        );
+        assert!(matches!(
+            registry.summary().outcomes[0].state,
+            BidOutcomeState::Submitted { bid_id: Some(id), .. } if id == U256::from(7u64)
+        ));
+    }
+
+    #[tokio::test]
+    async fn records_bids_that_differ_from_the_plan() {
+        let asserter = Asserter::new();
+        let mut registry = registry_with(&asserter, false, local_signer());
+        let event = CCA::BidSubmitted {
+            id: U256::from(7u64),
+            owner: SENDER,
+            price: U256::from(250u64),
+            amount: 1_000,
+        };
+        push_send(&asserter, &receipt(true, &[event.encode_log_data()]));
+        asserter.push_success(&Bytes::from(CCA::bidsCall::abi_encode_returns(&CCA::Bid {
+            startBlock: 16,
+            startCumulativeMps: Default::default(),
+            exitedBlock: 0,
+            maxPrice: U256::from(250u64),
+            owner: SENDER,
+            amountQ96: U256::from(999u64) << 96,
+            tokensFilled: U256::ZERO,
+        })));
+
+        attempt_bid(&mut registry.bids_mut()[0]).await;
+        assert!(asserter.read_q().is_empty());
+        let summary = registry.summary();
+        assert_eq!(summary.submitted, 1);
+        assert!(matches!(
+            &summary.outcomes[0].state,
+            BidOutcomeState::Submitted { mismatch: Some(mismatch), .. }
+                if mismatch == "bid 7 on chain differs from the plan: amount 999 (planned 1000)"
+        ));
+    }
+
+    #[tokio::test]
+    async fn stale_hints_fall_back_to_hintless_submit_bid() {
+        let asserter = Asserter::new();
+        let mut registry = registry(&asserter, true);
+        let stale = CCA::TickPreviousPriceInvalid {}.abi_encode();
+
+        // Stale hint, recompute, stale again, then hintless until a real revert.
+        push_revert(&asserter, stale.clone());
+        push_tick_walk(&asserter);
+        push_revert(&asserter, stale.clone());
+        push_revert(&asserter, CCA::BidMustBeAboveClearingPrice {}.abi_encode());
+
+        let tracked = &mut registry.bids_mut()[0];
+        let err = submit_bid(tracked).await.unwrap_err();
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(
+            BidFailure::from_report(&err).name.as_deref(),
+            Some("BidMustBeAboveClearingPrice")
+        );
+
+        let context = tracked.context_mut();
+        assert_eq!(context.prev_tick_hint, Some(U256::from(200u64)));
+        assert!(context.hintless);
+        let submit = context.prepare_submit_bid().await.unwrap();
+        let calldata = context.bid_calldata(&submit);
+        assert_eq!(calldata[..4], CCA::submitBid_0Call::SELECTOR);
+
+        // The next attempt sends with a hint again.
+        push_revert(&asserter, CCA::BidMustBeAboveClearingPrice {}.abi_encode());
+        assert!(submit_bid(tracked).await.is_err());
+        assert!(!tracked.context_mut().hintless);
+    }
+
+    #[tokio::test]
+    async fn confirms_bids_read_back_from_the_auction() {
+        let asserter = Asserter::new();
+        let mut registry = registry(&asserter, false);
+        let on_chain = CCA::Bid {
+            startBlock: 10,
+            startCumulativeMps: Default::default(),
+            exitedBlock: 0,
+            maxPrice: U256::from(250u64),
+            owner: SENDER,
+            amountQ96: U256::from(1_000u64) << 96,
+            tokensFilled: U256::ZERO,
+        };
+        let push_bid = |bid: &CCA::Bid| {
+            asserter.push_success(&Bytes::from(CCA::bidsCall::abi_encode_returns(bid)));
+        };
+
+        let context = registry.bids_mut()[0].context_mut();
+        push_bid(&on_chain);
+        context.confirm_bid(U256::from(7u64)).await.unwrap();
+
+        // Fractional Q96 bits below one currency unit are not a mismatch.
+        push_bid(&CCA::Bid {
+            amountQ96: (U256::from(1_000u64) << 96) + U256::from(1u64 << 40),
+            ..on_chain.clone()
+        });
+        context.confirm_bid(U256::from(7u64)).await.unwrap();
+
+        push_bid(&CCA::Bid {
+            amountQ96: U256::from(999u64) << 96,
+            ..on_chain.clone()
+        });
+        let err = context.confirm_bid(U256::from(7u64)).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "bid 7 on chain differs from the plan: amount 999 (planned 1000)"
+        );
+
+        let other = Address::repeat_byte(0x0b);
+        push_bid(&CCA::Bid {
+            maxPrice: U256::from(300u64),
+            owner: other,
+            ..on_chain
+        });
+        let err = context.confirm_bid(U256::from(7u64)).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "bid 7 on chain differs from the plan: max price 300 (planned 250), owner {other} (planned {SENDER})"
+            )
+        );
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stale_hints_give_up_after_two_recoveries_without_fallback() {
+        let asserter = Asserter::new();
+        let mut registry = registry(&asserter, false);
+        let stale = CCA::TickPreviousPriceInvalid {}.abi_encode();
+        for _ in 0..2 {
+            push_revert(&asserter, stale.clone());
+            push_tick_walk(&asserter);
+        }
+        push_revert(&asserter, stale);
+
+        let tracked = &mut registry.bids_mut()[0];
+        let err = submit_bid(tracked).await.unwrap_err();
+        assert!(asserter.read_q().is_empty());
+        assert!(is_stale_hint(&err));
+        assert!(!tracked.context_mut().hintless);
+    }
+}
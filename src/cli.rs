@@ -0,0 +1,250 @@
+use crate::{
+    app::{AuctionBot, AuctionSetup},
+    blocks::simulate_bid,
+    config::{Config, ConfigOverrides, transport_from_env},
+    logging::load_summary,
+    network::Network,
+    offline::{DEFAULT_OFFLINE_GAS_LIMIT, broadcast, sign_bids},
+    report::{
+        print_broadcast, print_plan, print_safe_batch, print_signed, print_simulation,
+        print_status, print_summary,
+    },
+    safe::{build_safe_batch, write_safe_batch},
+};
+use alloy::{
+    primitives::{Address, U256},
+    providers::{DynProvider, Provider, ProviderBuilder},
+    rpc::client::BuiltInConnectionString,
+};
+use clap::{Args, Parser, Subcommand};
+use eyre::{Result, eyre};
+use std::{path::PathBuf, str::FromStr};
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Bid bot for Aztec's Uniswap Continuous Clearing Auction"
+)]
+pub struct Cli {
+    #[command(flatten)]
+    overrides: OverrideArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Stream blocks and submit bids once the public track opens (default).
+    Run,
+    /// Print the resolved, tick-aligned bids without submitting anything.
+    Plan,
+    /// Prepare, build and simulate every bid without sending.
+    Simulate,
+    /// Print the live auction parameters and phase.
+    Status,
+    /// Sign every planned bid against a chain snapshot and write the raw
+    /// transactions plus a manifest to disk without broadcasting.
+    Sign {
+        /// Output directory for the raw transactions and `manifest.json`.
+        #[arg(long, default_value = "signed")]
+        out: PathBuf,
+        /// Gas limit for every signed bid.
+        #[arg(long, default_value_t = DEFAULT_OFFLINE_GAS_LIMIT)]
+        gas_limit: u64,
+    },
+    /// Write every planned bid as a Safe Transaction Builder batch for SAFE_ADDRESS.
+    Safe {
+        /// Output path for the batch JSON.
+        #[arg(long, default_value = "safe-batch.json")]
+        out: PathBuf,
+    },
+    /// Push transactions written by `sign` once the public track opens.
+    Broadcast {
+        /// Directory holding `manifest.json` and the raw transactions.
+        dir: PathBuf,
+    },
+    /// Pretty-print a persisted `cca-summary-*.json` file.
+    Summary {
+        /// Path to the summary file.
+        file: PathBuf,
+    },
+}
+
+/// Commands that run against the bid config, once it has been loaded.
+#[derive(Debug)]
+enum ConfiguredCommand {
+    Run,
+    Plan,
+    Simulate,
+    Status,
+    Sign { out: PathBuf, gas_limit: u64 },
+    Safe { out: PathBuf },
+}
+
+/// Flags that take precedence over `.env` values.
+#[derive(Debug, Args)]
+struct OverrideArgs {
+    /// RPC endpoint (HTTP/WS URL or IPC path), overrides RPC_ENDPOINT.
+    #[arg(long, global = true)]
+    rpc_endpoint: Option<BuiltInConnectionString>,
+
+    /// Network profile (mainnet, sepolia, custom), overrides NETWORK.
+    #[arg(long, global = true)]
+    network: Option<String>,
+
+    /// Expected chain ID, overrides CHAIN_ID.
+    #[arg(long, global = true)]
+    chain_id: Option<u64>,
+
+    /// CCA contract address, overrides CCA_ADDRESS.
+    #[arg(long, global = true)]
+    cca_address: Option<Address>,
+
+    /// Default bid owner, overrides OWNER.
+    #[arg(long, global = true)]
+    owner: Option<Address>,
+
+    /// Bids file, overrides BIDS_FILE.
+    #[arg(long, global = true)]
+    bids: Option<PathBuf>,
+
+    /// Encrypted JSON keystore to sign with, overrides KEYSTORE_PATH.
+    #[arg(long, global = true)]
+    keystore: Option<PathBuf>,
+
+    /// Safe multisig that sends the bids, overrides SAFE_ADDRESS.
+    #[arg(long, global = true)]
+    safe: Option<Address>,
+}
+
+impl OverrideArgs {
+    fn into_overrides(self) -> Result<ConfigOverrides> {
+        Ok(ConfigOverrides {
+            rpc_endpoint: self.rpc_endpoint,
+            network: self.network.as_deref().map(Network::from_str).transpose()?,
+            chain_id: self.chain_id,
+            cca: self.cca_address,
+            owner: self.owner,
+            bids_file: self.bids,
+            keystore: self.keystore,
+            safe: self.safe,
+        })
+    }
+}
+
+impl Cli {
+    pub async fn execute(self) -> Result<()> {
+        let command = match self.command.unwrap_or(Command::Run) {
+            Command::Summary { file } => {
+                print_summary(&load_summary(&file)?);
+                return Ok(());
+            }
+            Command::Broadcast { dir } => {
+                let overrides = self.overrides.into_overrides()?;
+                let transport = transport_from_env(&overrides)?;
+                let provider = connect(&transport).await?;
+                let results = broadcast(provider, &transport, &dir).await?;
+                print_broadcast(&results);
+                return Ok(());
+            }
+            Command::Run => ConfiguredCommand::Run,
+            Command::Plan => ConfiguredCommand::Plan,
+            Command::Simulate => ConfiguredCommand::Simulate,
+            Command::Status => ConfiguredCommand::Status,
+            Command::Sign { out, gas_limit } => ConfiguredCommand::Sign { out, gas_limit },
+            Command::Safe { out } => ConfiguredCommand::Safe { out },
+        };
+
+        let overrides = self.overrides.into_overrides()?;
+        let config = Config::from_env(&overrides)?;
+        if let (ConfiguredCommand::Run | ConfiguredCommand::Sign { .. }, Some(safe)) =
+            (&command, config.safe)
+        {
+            return Err(eyre!(
+                "bids are sent by Safe {safe}, use the `safe` command to export them"
+            ));
+        }
+        let provider = connect(&config.transport).await?;
+
+        match command {
+            ConfiguredCommand::Run => {
+                AuctionBot::build_with_provider(provider, config)
+                    .await?
+                    .run()
+                    .await
+            }
+            ConfiguredCommand::Plan => {
+                let setup = AuctionSetup::load(provider, &config).await?;
+                print_plan(&setup.params, &setup.planned_bids, &setup.budgets);
+                Ok(())
+            }
+            ConfiguredCommand::Simulate => simulate(provider, &config).await,
+            ConfiguredCommand::Status => {
+                let auction = AuctionSetup::connect(provider.clone(), &config).await?;
+                let params = auction.load_params(&config.senders.addresses()).await?;
+                let block_number = provider.get_block_number().await?;
+                let phase = params.window().phase(U256::from(block_number));
+                let state = auction
+                    .load_state(&config.senders.addresses(), block_number)
+                    .await?;
+                print_status(block_number, phase, &params, &state);
+                Ok(())
+            }
+            ConfiguredCommand::Sign { out, gas_limit } => {
+                let mut registry = AuctionSetup::load(provider.clone(), &config)
+                    .await?
+                    .into_registry(&config)?;
+                let manifest = sign_bids(
+                    &provider,
+                    &mut registry,
+                    config.network.cca,
+                    gas_limit,
+                    &out,
+                )
+                .await?;
+                print_signed(&manifest, &out);
+                Ok(())
+            }
+            ConfiguredCommand::Safe { out } => {
+                let Some(safe) = config.safe else {
+                    return Err(eyre!("the `safe` command requires SAFE_ADDRESS or --safe"));
+                };
+                let mut registry = AuctionSetup::load(provider.clone(), &config)
+                    .await?
+                    .into_registry(&config)?;
+                let batch =
+                    build_safe_batch(&provider, &mut registry, safe, config.network.cca).await?;
+                write_safe_batch(&batch, &out)?;
+                print_safe_batch(&batch, &out);
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn connect(transport: &BuiltInConnectionString) -> Result<DynProvider> {
+    let provider = ProviderBuilder::new().connect_with(transport).await?;
+    Ok(provider.erased())
+}
+
+async fn simulate(provider: DynProvider, config: &Config) -> Result<()> {
+    let mut registry = AuctionSetup::load(provider, config)
+        .await?
+        .into_registry(config)?;
+    registry.resolve_hints(None).await?;
+
+    let mut failed = 0;
+    for tracked in registry.bids_mut().iter_mut() {
+        let result = simulate_bid(tracked).await;
+        if result.is_err() {
+            failed += 1;
+        }
+        print_simulation(tracked.bid_params(), &result);
+    }
+
+    if failed > 0 {
+        return Err(eyre!("{failed} bid(s) failed simulation"));
+    }
+    Ok(())
+}
@@ -1,109 +1,689 @@
+use crate::{
+    auction::AuctionParams,
+    bids::{TickRounding, align_price_to_tick},
+    ladder::{LadderSpacing, LadderTemplate, LadderWeighting},
+    network::{Network, NetworkOverrides, NetworkProfile},
+    schedule::{BidWindow, BlockRefSpec, WindowSpec},
+    senders::{SenderRef, Senders},
+    signer::{LocalSigner, SafeAccount},
+    transaction::{AccessListConfig, TxConfig},
+    units::{AmountSpec, AmountUnit, CurrencyInfo, Decimal},
+};
 use alloy::{
     primitives::{Address, U256},
-    rpc::client::BuiltInConnectionString,
+    rpc::{client::BuiltInConnectionString, types::transaction::AccessList},
     signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
 };
 use eyre::{Result, WrapErr, eyre};
 use serde::Deserialize;
-use std::{env::VarError, fs, path::Path, str::FromStr};
+use std::{
+    env::VarError,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 const DEFAULT_BIDS_FILE: &str = "bids.toml";
 
 #[derive(Debug)]
 pub struct Config {
     pub transport: BuiltInConnectionString,
-    pub signer: PrivateKeySigner,
-    pub bids: Vec<BidParams>,
+    pub senders: Senders,
+    /// Safe multisig that sends every bid; bids are exported as a batch instead of sent.
+    pub safe: Option<Address>,
+    pub network: NetworkProfile,
+    pub bids: Vec<BidTemplate>,
+    pub ladders: Vec<LadderTemplate>,
+}
+
+/// Values supplied on the command line, taking precedence over `.env`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub rpc_endpoint: Option<BuiltInConnectionString>,
+    pub network: Option<Network>,
+    pub chain_id: Option<u64>,
+    pub cca: Option<Address>,
+    pub owner: Option<Address>,
+    pub bids_file: Option<PathBuf>,
+    pub keystore: Option<PathBuf>,
+    pub safe: Option<Address>,
+}
+
+/// Maximum price of a bid, either as a raw Q96 value or as a decimal price in
+/// currency per token that still needs the on-chain decimals to resolve.
+#[derive(Debug, Clone)]
+pub enum BidPrice {
+    Q96(U256),
+    Decimal(Decimal),
+}
+
+/// A bid as written in the config file, before on-chain values are applied.
+#[derive(Debug, Clone)]
+pub struct BidTemplate {
+    pub label: String,
+    pub price: BidPrice,
+    pub amount: AmountSpec,
+    /// Unit applied when `amount` is written without one.
+    pub amount_unit: AmountUnit,
+    pub owner: Address,
+    pub sender: Address,
+    pub tx: TxSettings,
+    pub window: WindowSpec,
+    pub rounding: TickRounding,
+}
+
+impl BidTemplate {
+    pub fn resolve(&self, idx: usize, params: &AuctionParams) -> Result<BidParams> {
+        let bid_no = idx + 1;
+
+        let requested_max_bid = match &self.price {
+            BidPrice::Q96(max_bid) => *max_bid,
+            BidPrice::Decimal(price) => params.price_scale.to_q96(price).map_err(|err| {
+                eyre!("bid #{bid_no} price {price} cannot be converted to Q96: {err}")
+            })?,
+        };
+        let max_bid = align_price_to_tick(requested_max_bid, params, self.rounding)
+            .map_err(|err| eyre!("bid #{bid_no} {err}"))?;
+
+        let amount = self
+            .amount
+            .to_base_units(&self.amount_unit, &params.currency)
+            .map_err(|err| eyre!("bid #{bid_no} amount {} is invalid: {err}", self.amount))?;
+
+        let window = self
+            .window
+            .resolve(params)
+            .map_err(|err| eyre!("bid #{bid_no} block window is invalid: {err}"))?;
+
+        Ok(BidParams {
+            label: self.label.clone(),
+            max_bid,
+            requested_max_bid,
+            amount,
+            owner: self.owner,
+            sender: self.sender,
+            tx: self.tx.clone(),
+            window,
+            rounding: self.rounding,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BidParams {
+    pub label: String,
+    /// Tick-aligned max bid that is sent.
     pub max_bid: U256,
+    /// Max bid as configured, before tick alignment.
+    pub requested_max_bid: U256,
     pub amount: u128,
     pub owner: Address,
+    /// Account that signs and pays for the bid.
+    pub sender: Address,
+    pub tx: TxSettings,
+    pub window: BidWindow,
+    pub rounding: TickRounding,
+}
+
+/// Transaction settings for a bid, merged from the global and per-bid `[tx]` sections.
+#[derive(Debug, Clone, Default)]
+pub struct TxSettings {
+    pub tx_config: Option<TxConfig>,
+    pub max_retries: Option<u8>,
+    /// Fall back to the `submitBid` overload without `prevTickPrice` once a
+    /// recomputed hint is rejected too.
+    pub hint_fallback: bool,
 }
 
 impl Config {
-    pub fn from_env() -> Result<Self> {
-        dotenvy::dotenv().ok();
-
-        let transport = provider_transport_from_env()?;
-
-        let signer = parse_env("PRIVATE_KEY", "hex private key", |value| {
-            PrivateKeySigner::from_str(value)
-                .map_err(|_| eyre!("PRIVATE_KEY is not a valid private key"))
-        })?;
-
-        let owner = match optional_env("OWNER", |value| {
-            Address::parse_checksummed(value, None)
-                .map_err(|_| eyre!("OWNER is not a valid checksummed address: {value}"))
-        })? {
-            Some(address) => address,
-            None => signer.address(),
+    pub fn from_env(overrides: &ConfigOverrides) -> Result<Self> {
+        let transport = transport_from_env(overrides)?;
+        let network = network_from_env(overrides)?;
+
+        let safe = match overrides.safe {
+            Some(address) => Some(address),
+            None => optional_address_env("SAFE_ADDRESS")?,
+        };
+        // A Safe sends every bid itself, so no local or remote keys are loaded.
+        let senders = match safe {
+            Some(address) => Senders::single(Arc::new(SafeAccount::new(address))),
+            None => senders_from_env(overrides)?,
         };
 
-        let bids = load_bids(owner)?;
+        let owner = match overrides.owner {
+            Some(address) => Some(address),
+            None => optional_address_env("OWNER")?,
+        };
+
+        let bids_file = match &overrides.bids_file {
+            Some(path) => path.clone(),
+            None => optional_env("BIDS_FILE", |value| Ok(PathBuf::from(value)))?
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_BIDS_FILE)),
+        };
+
+        let (bids, ladders) = load_bids(&bids_file, owner, &senders)?;
 
         Ok(Self {
             transport,
             bids,
-            signer,
+            ladders,
+            senders,
+            safe,
+            network,
         })
     }
 }
 
-fn load_bids(default_owner: Address) -> Result<Vec<BidParams>> {
-    let path = Path::new(DEFAULT_BIDS_FILE);
+fn load_bids(
+    path: &Path,
+    default_owner: Option<Address>,
+    senders: &Senders,
+) -> Result<(Vec<BidTemplate>, Vec<LadderTemplate>)> {
     let contents = fs::read_to_string(path)
         .wrap_err(format!("failed to read bids config at {}", path.display()))?;
     let file: BidFile =
         toml::from_str(&contents).wrap_err("failed to parse bids config (expected TOML format)")?;
-    if file.bids.is_empty() {
+    if file.bids.is_empty() && file.ladders.is_empty() {
         return Err(eyre!(
-            "bids config must include at least one [[bids]] entry"
+            "bids config must include at least one [[bids]] or [[ladders]] entry"
         ));
     }
 
-    file.bids
+    let amount_unit = match &file.amount_unit {
+        Some(raw) => AmountUnit::from_str(raw)
+            .map_err(|err| eyre!("amount_unit is not a valid unit: {err}"))?,
+        None => AmountUnit::Wei,
+    };
+    let defaults = EntryDefaults {
+        owner: default_owner,
+        senders,
+        amount_unit,
+        rounding: file.tick_rounding.unwrap_or_default(),
+        tx: file.tx.unwrap_or_default(),
+    };
+
+    let templates = file
+        .bids
         .into_iter()
-        .map(|bid| bid.into_params(default_owner))
-        .collect()
+        .enumerate()
+        .map(|(idx, bid)| bid.into_template(idx, &defaults))
+        .collect::<Result<Vec<_>>>()?;
+
+    let ladders = file
+        .ladders
+        .into_iter()
+        .enumerate()
+        .map(|(idx, ladder)| ladder.into_template(idx, &defaults))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((templates, ladders))
+}
+
+/// File-level defaults applied to every `[[bids]]` and `[[ladders]]` entry.
+struct EntryDefaults<'a> {
+    /// `OWNER` override; without it a bid is owned by its sender.
+    owner: Option<Address>,
+    senders: &'a Senders,
+    amount_unit: AmountUnit,
+    rounding: TickRounding,
+    tx: TxSpec,
 }
 
 #[derive(Debug, Deserialize)]
 struct BidFile {
+    /// Default unit for amounts without one; `wei` keeps raw integers working.
+    amount_unit: Option<String>,
+    /// Default tick rounding for bids and ladders without their own.
+    tick_rounding: Option<TickRounding>,
+    tx: Option<TxSpec>,
+    #[serde(default)]
     bids: Vec<BidSpec>,
+    #[serde(default)]
+    ladders: Vec<LadderSpec>,
 }
 
 #[derive(Debug, Deserialize)]
 struct BidSpec {
-    max_bid: String,
+    #[serde(alias = "id")]
+    label: Option<String>,
+    max_bid: Option<String>,
+    price: Option<String>,
     amount: String,
     owner: Option<String>,
+    sender: Option<SenderRef>,
+    tx: Option<TxSpec>,
+    not_before_block: Option<BlockRefSpec>,
+    not_after_block: Option<BlockRefSpec>,
+    tick_rounding: Option<TickRounding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LadderSpec {
+    #[serde(alias = "id")]
+    label: Option<String>,
+    min_price: String,
+    max_price: String,
+    steps: Option<usize>,
+    spacing: LadderSpacing,
+    every_ticks: Option<u64>,
+    amount: String,
+    #[serde(default)]
+    weighting: LadderWeighting,
+    owner: Option<String>,
+    sender: Option<SenderRef>,
+    tx: Option<TxSpec>,
+    not_before_block: Option<BlockRefSpec>,
+    not_after_block: Option<BlockRefSpec>,
+    tick_rounding: Option<TickRounding>,
+}
+
+impl LadderSpec {
+    fn into_template(self, idx: usize, defaults: &EntryDefaults) -> Result<LadderTemplate> {
+        let label = match self.label {
+            Some(label) if label.trim().is_empty() => {
+                return Err(eyre!("ladder #{} label cannot be empty", idx + 1));
+            }
+            Some(label) => label.trim().to_string(),
+            None => format!("ladder-{}", idx + 1),
+        };
+        let entry = format!("ladder {label}");
+
+        let price = |raw: &str, field: &str| {
+            Decimal::from_str(raw)
+                .map_err(|_| eyre!("{entry} {field} is not a valid decimal price: {raw}"))
+        };
+        let min_price = price(&self.min_price, "min_price")?;
+        let max_price = price(&self.max_price, "max_price")?;
+
+        let amount = AmountSpec::from_str(&self.amount)
+            .map_err(|err| eyre!("{entry} amount is not a valid amount: {err}"))?;
+        let sender = parse_sender(self.sender.as_ref(), &entry, defaults.senders)?;
+        let owner = parse_owner(self.owner, &entry, defaults.owner.unwrap_or(sender))?;
+        let tx = self
+            .tx
+            .unwrap_or_default()
+            .or(&defaults.tx)
+            .into_settings(&entry)?;
+        let window = parse_window(self.not_before_block, self.not_after_block, &entry)?;
+
+        Ok(LadderTemplate {
+            label,
+            min_price,
+            max_price,
+            steps: self.steps,
+            spacing: self.spacing,
+            every_ticks: self.every_ticks,
+            amount,
+            amount_unit: defaults.amount_unit.clone(),
+            weighting: self.weighting,
+            owner,
+            sender,
+            tx,
+            window,
+            rounding: self.tick_rounding.unwrap_or(defaults.rounding),
+        })
+    }
+}
+
+fn parse_window(
+    not_before: Option<BlockRefSpec>,
+    not_after: Option<BlockRefSpec>,
+    entry: &str,
+) -> Result<WindowSpec> {
+    let parse = |spec: Option<BlockRefSpec>, field: &str| {
+        spec.map(|spec| spec.parse())
+            .transpose()
+            .map_err(|err| eyre!("{entry} {field} is invalid: {err}"))
+    };
+    Ok(WindowSpec {
+        not_before: parse(not_before, "not_before_block")?,
+        not_after: parse(not_after, "not_after_block")?,
+    })
+}
+
+fn parse_sender(raw: Option<&SenderRef>, entry: &str, senders: &Senders) -> Result<Address> {
+    match raw {
+        Some(sender) => senders
+            .resolve(sender)
+            .map_err(|err| eyre!("{entry} {err}")),
+        None => Ok(senders.default_sender().address()),
+    }
+}
+
+fn parse_owner(raw: Option<String>, entry: &str, default_owner: Address) -> Result<Address> {
+    match raw {
+        Some(raw) => Address::parse_checksummed(raw.trim(), None)
+            .map_err(|_| eyre!("{entry} owner is not a valid checksummed address: {raw}")),
+        None => Ok(default_owner),
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TxSpec {
+    max_fee: Option<String>,
+    priority_fee: Option<String>,
+    access_list: Option<AccessListMode>,
+    access_list_entries: Option<AccessList>,
+    max_retries: Option<u8>,
+    hint_fallback: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AccessListMode {
+    None,
+    Generate,
+    Provided,
+}
+
+impl TxSpec {
+    /// Fills every field left unset here from `fallback`. The access-list mode
+    /// and its entries are one unit: setting the mode drops the fallback entries.
+    fn or(self, fallback: &TxSpec) -> TxSpec {
+        let (access_list, access_list_entries) = match self.access_list {
+            Some(mode) => (Some(mode), self.access_list_entries),
+            None => (
+                fallback.access_list,
+                self.access_list_entries
+                    .or_else(|| fallback.access_list_entries.clone()),
+            ),
+        };
+        TxSpec {
+            max_fee: self.max_fee.or_else(|| fallback.max_fee.clone()),
+            priority_fee: self.priority_fee.or_else(|| fallback.priority_fee.clone()),
+            access_list,
+            access_list_entries,
+            max_retries: self.max_retries.or(fallback.max_retries),
+            hint_fallback: self.hint_fallback.or(fallback.hint_fallback),
+        }
+    }
+
+    fn into_settings(self, entry: &str) -> Result<TxSettings> {
+        let fee = |raw: Option<String>, field: &str| -> Result<Option<u128>> {
+            raw.map(|raw| {
+                AmountSpec::from_str(&raw)
+                    .and_then(|fee| fee.to_base_units(&AmountUnit::Wei, &CurrencyInfo::native()))
+                    .map_err(|err| eyre!("{entry} tx.{field} is not a valid fee: {err}"))
+            })
+            .transpose()
+        };
+        let max_fee = fee(self.max_fee, "max_fee")?;
+        let priority_fee = fee(self.priority_fee, "priority_fee")?;
+
+        let mut tx_config = match (max_fee, priority_fee) {
+            (Some(max_fee), Some(priority_fee)) => {
+                if priority_fee > max_fee {
+                    return Err(eyre!(
+                        "{entry} tx.priority_fee ({priority_fee}) exceeds tx.max_fee ({max_fee})"
+                    ));
+                }
+                Some(TxConfig::new().with_fee_overrides(max_fee, priority_fee))
+            }
+            (None, None) => None,
+            _ => {
+                return Err(eyre!(
+                    "{entry} tx.max_fee and tx.priority_fee must be set together"
+                ));
+            }
+        };
+
+        let access_list = match (self.access_list, self.access_list_entries) {
+            (None | Some(AccessListMode::None), None) => None,
+            (Some(AccessListMode::Generate), None) => Some(AccessListConfig::Generate),
+            (Some(AccessListMode::Provided), Some(list)) => Some(AccessListConfig::Provided(list)),
+            (Some(AccessListMode::Provided), None) => {
+                return Err(eyre!(
+                    "{entry} tx.access_list = \"provided\" requires tx.access_list_entries"
+                ));
+            }
+            (_, Some(_)) => {
+                return Err(eyre!(
+                    "{entry} tx.access_list_entries requires tx.access_list = \"provided\""
+                ));
+            }
+        };
+        if let Some(access_list) = access_list {
+            tx_config = Some(tx_config.unwrap_or_default().with_access_list(access_list));
+        }
+
+        if self.max_retries == Some(0) {
+            return Err(eyre!("{entry} tx.max_retries must be at least 1"));
+        }
+
+        Ok(TxSettings {
+            tx_config,
+            max_retries: self.max_retries,
+            hint_fallback: self.hint_fallback.unwrap_or(false),
+        })
+    }
 }
 
 impl BidSpec {
-    fn into_params(self, default_owner: Address) -> Result<BidParams> {
-        let max_bid = U256::from_str(self.max_bid.trim())
-            .map_err(|_| eyre!("bid entry max_bid is not a valid U256: {}", self.max_bid))?;
+    fn into_template(self, idx: usize, defaults: &EntryDefaults) -> Result<BidTemplate> {
+        let bid_no = idx + 1;
+
+        let price =
+            match (self.max_bid, self.price) {
+                (Some(raw), None) => BidPrice::Q96(
+                    U256::from_str(raw.trim())
+                        .map_err(|_| eyre!("bid #{bid_no} max_bid is not a valid U256: {raw}"))?,
+                ),
+                (None, Some(raw)) => BidPrice::Decimal(Decimal::from_str(&raw).map_err(|_| {
+                    eyre!("bid #{bid_no} price is not a valid decimal price: {raw}")
+                })?),
+                (Some(_), Some(_)) => {
+                    return Err(eyre!("bid #{bid_no} sets both max_bid and price, pick one"));
+                }
+                (None, None) => return Err(eyre!("bid #{bid_no} must set max_bid or price")),
+            };
 
-        let amount = u128::from_str(self.amount.trim())
-            .map_err(|_| eyre!("bid entry amount is not a valid u128: {}", self.amount))?;
+        let amount = AmountSpec::from_str(&self.amount)
+            .map_err(|err| eyre!("bid #{bid_no} amount is not a valid amount: {err}"))?;
 
-        let owner = match self.owner {
-            Some(raw) => Address::parse_checksummed(raw.trim(), None)
-                .map_err(|_| eyre!("bid entry owner is not a valid checksummed address: {raw}"))?,
-            None => default_owner,
+        let entry = format!("bid #{bid_no}");
+        let sender = parse_sender(self.sender.as_ref(), &entry, defaults.senders)?;
+        let owner = parse_owner(self.owner, &entry, defaults.owner.unwrap_or(sender))?;
+        let tx = self
+            .tx
+            .unwrap_or_default()
+            .or(&defaults.tx)
+            .into_settings(&entry)?;
+        let window = parse_window(self.not_before_block, self.not_after_block, &entry)?;
+
+        let label = match self.label {
+            Some(label) if label.trim().is_empty() => {
+                return Err(eyre!("bid #{bid_no} label cannot be empty"));
+            }
+            Some(label) => label.trim().to_string(),
+            None => bid_no.to_string(),
         };
 
-        Ok(BidParams {
-            max_bid,
+        Ok(BidTemplate {
+            label,
+            price,
             amount,
+            amount_unit: defaults.amount_unit.clone(),
             owner,
+            sender,
+            tx,
+            window,
+            rounding: self.tick_rounding.unwrap_or(defaults.rounding),
         })
     }
 }
 
+/// Where the signing accounts are loaded from.
+enum SignerSource {
+    Keystore(PathBuf),
+    MnemonicFile(PathBuf),
+    Mnemonic(String),
+    Remote(Url),
+    PrivateKey(String),
+}
+
+/// Reads one environment variable, `None` when it is unset.
+type EnvLookup<'a> = dyn Fn(&str) -> Result<Option<String>> + 'a;
+
+/// Loads the accounts to sign with from the source `signer_source` picks.
+fn senders_from_env(overrides: &ConfigOverrides) -> Result<Senders> {
+    match signer_source(&process_env, overrides.keystore.as_deref())? {
+        SignerSource::Keystore(path) => {
+            let password = match keystore_password(&process_env)? {
+                Some(password) => password,
+                None => rpassword::prompt_password("Keystore password: ")
+                    .wrap_err("failed to read keystore password")?,
+            };
+            senders_from_keystore(&path, password)
+        }
+        SignerSource::Remote(url) => {
+            let addresses = parse_env(
+                "REMOTE_SIGNER_ADDRESSES",
+                "comma-separated accounts held by the remote signer",
+                parse_remote_addresses,
+            )?;
+            Senders::remote(url, &addresses)
+        }
+        SignerSource::MnemonicFile(path) => {
+            let phrase = fs::read_to_string(&path).wrap_err(format!(
+                "failed to read mnemonic file at {}",
+                path.display()
+            ))?;
+            senders_from_mnemonic(phrase.trim())
+        }
+        SignerSource::Mnemonic(phrase) => senders_from_mnemonic(&phrase),
+        SignerSource::PrivateKey(key) => {
+            let signer = PrivateKeySigner::from_str(&key)
+                .map_err(|_| eyre!("PRIVATE_KEY is not a valid private key"))?;
+            Ok(Senders::single(Arc::new(LocalSigner::new(signer))))
+        }
+    }
+}
+
+/// Picks exactly one signer source: an encrypted keystore (`--keystore` or
+/// `KEYSTORE_PATH`), a mnemonic (`MNEMONIC_FILE`/`MNEMONIC` plus
+/// `MNEMONIC_INDICES`), a remote signer (`REMOTE_SIGNER_URL` plus
+/// `REMOTE_SIGNER_ADDRESSES`), or a raw `PRIVATE_KEY`. Plaintext secrets in the
+/// environment are only accepted with `ALLOW_PLAINTEXT_KEY=true`.
+fn signer_source(env: &EnvLookup, keystore: Option<&Path>) -> Result<SignerSource> {
+    let keystore = match keystore {
+        Some(path) => Some(path.to_path_buf()),
+        None => optional_var(env, "KEYSTORE_PATH", |value| Ok(PathBuf::from(value)))?,
+    };
+    let mnemonic_file = optional_var(env, "MNEMONIC_FILE", |value| Ok(PathBuf::from(value)))?;
+    let mnemonic = optional_var(env, "MNEMONIC", |value| Ok(value.to_string()))?;
+    let remote_url = optional_var(env, "REMOTE_SIGNER_URL", |value| {
+        Url::parse(value).map_err(|err| eyre!("REMOTE_SIGNER_URL is not a valid URL: {err}"))
+    })?;
+    let private_key = optional_var(env, "PRIVATE_KEY", |value| Ok(value.to_string()))?;
+
+    let sources = [
+        keystore.is_some(),
+        mnemonic_file.is_some() || mnemonic.is_some(),
+        remote_url.is_some(),
+        private_key.is_some(),
+    ];
+    if sources.iter().filter(|set| **set).count() > 1 {
+        return Err(eyre!(
+            "set only one of KEYSTORE_PATH, MNEMONIC_FILE/MNEMONIC, REMOTE_SIGNER_URL or PRIVATE_KEY"
+        ));
+    }
+
+    if let Some(path) = keystore {
+        return Ok(SignerSource::Keystore(path));
+    }
+    if let Some(url) = remote_url {
+        return Ok(SignerSource::Remote(url));
+    }
+    match (mnemonic_file, mnemonic) {
+        (Some(_), Some(_)) => {
+            return Err(eyre!(
+                "both MNEMONIC_FILE and MNEMONIC are set, remove one of them"
+            ));
+        }
+        (Some(path), None) => return Ok(SignerSource::MnemonicFile(path)),
+        (None, Some(phrase)) => {
+            ensure_plaintext_allowed(env, "MNEMONIC", "MNEMONIC_FILE")?;
+            return Ok(SignerSource::Mnemonic(phrase));
+        }
+        (None, None) => {}
+    }
+    if let Some(key) = private_key {
+        ensure_plaintext_allowed(env, "PRIVATE_KEY", "KEYSTORE_PATH")?;
+        return Ok(SignerSource::PrivateKey(key));
+    }
+
+    Err(eyre!(
+        "missing KEYSTORE_PATH (encrypted JSON keystore), MNEMONIC_FILE or REMOTE_SIGNER_URL to load signers from (or PRIVATE_KEY/MNEMONIC with ALLOW_PLAINTEXT_KEY=true)"
+    ))
+}
+
+fn ensure_plaintext_allowed(env: &EnvLookup, key: &str, alternative: &str) -> Result<()> {
+    let allowed = optional_var(env, "ALLOW_PLAINTEXT_KEY", |value| {
+        value
+            .parse::<bool>()
+            .map_err(|_| eyre!("ALLOW_PLAINTEXT_KEY must be true or false"))
+    })?
+    .unwrap_or(false);
+    if !allowed {
+        return Err(eyre!(
+            "{key} is only read with ALLOW_PLAINTEXT_KEY=true, use {alternative} instead"
+        ));
+    }
+    Ok(())
+}
+
+fn senders_from_keystore(path: &Path, password: String) -> Result<Senders> {
+    let signer = PrivateKeySigner::decrypt_keystore(path, password)
+        .map_err(|err| eyre!("failed to decrypt keystore at {}: {err}", path.display()))?;
+    Ok(Senders::single(Arc::new(LocalSigner::new(signer))))
+}
+
+fn senders_from_mnemonic(phrase: &str) -> Result<Senders> {
+    let indices = optional_env("MNEMONIC_INDICES", parse_indices)?.unwrap_or_else(|| vec![0]);
+    Senders::from_mnemonic(phrase, &indices)
+}
+
+/// Parses `MNEMONIC_INDICES`, a comma-separated list such as `0,1,4`.
+fn parse_indices(value: &str) -> Result<Vec<u32>> {
+    value
+        .split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<u32>()
+                .map_err(|_| eyre!("MNEMONIC_INDICES entry {part} is not a valid index"))
+        })
+        .collect()
+}
+
+/// Keystore password from `KEYSTORE_PASSWORD_FILE`, then `KEYSTORE_PASSWORD`.
+/// `None` means neither is set and the password has to be prompted for.
+fn keystore_password(env: &EnvLookup) -> Result<Option<String>> {
+    if let Some(path) = optional_var(env, "KEYSTORE_PASSWORD_FILE", |value| {
+        Ok(PathBuf::from(value))
+    })? {
+        let contents = fs::read_to_string(&path).wrap_err(format!(
+            "failed to read keystore password file at {}",
+            path.display()
+        ))?;
+        return Ok(Some(contents.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    // Read raw like the file: spaces can be part of the password.
+    Ok(env("KEYSTORE_PASSWORD")?
+        .map(|raw| raw.trim_end_matches(['\r', '\n']).to_string())
+        .filter(|password| !password.is_empty()))
+}
+
+/// RPC endpoint alone, for commands that only broadcast and need no signer or bids.
+pub fn transport_from_env(overrides: &ConfigOverrides) -> Result<BuiltInConnectionString> {
+    dotenvy::dotenv().ok();
+    match &overrides.rpc_endpoint {
+        Some(transport) => Ok(transport.clone()),
+        None => provider_transport_from_env(),
+    }
+}
+
 fn provider_transport_from_env() -> Result<BuiltInConnectionString> {
     parse_env("RPC_ENDPOINT", "HTTP/WS URL or IPC path", |value| {
         value
@@ -112,6 +692,37 @@ fn provider_transport_from_env() -> Result<BuiltInConnectionString> {
     })
 }
 
+fn network_from_env(overrides: &ConfigOverrides) -> Result<NetworkProfile> {
+    let network = match overrides.network {
+        Some(network) => network,
+        None => optional_env("NETWORK", Network::from_str)?.unwrap_or(Network::Mainnet),
+    };
+    let chain_id = match overrides.chain_id {
+        Some(chain_id) => Some(chain_id),
+        None => optional_env("CHAIN_ID", |value| {
+            u64::from_str(value).map_err(|_| eyre!("CHAIN_ID is not a valid u64: {value}"))
+        })?,
+    };
+    let cca = match overrides.cca {
+        Some(cca) => Some(cca),
+        None => optional_address_env("CCA_ADDRESS")?,
+    };
+    let network_overrides = NetworkOverrides {
+        chain_id,
+        cca,
+        hook: optional_address_env("HOOK_ADDRESS")?,
+        soulbound: optional_address_env("SOULBOUND_ADDRESS")?,
+    };
+    NetworkProfile::resolve(network, network_overrides)
+}
+
+fn optional_address_env(key: &str) -> Result<Option<Address>> {
+    optional_env(key, |value| {
+        Address::parse_checksummed(value, None)
+            .map_err(|_| eyre!("{key} is not a valid checksummed address: {value}"))
+    })
+}
+
 fn parse_env<T, F>(key: &str, desc: &str, parser: F) -> Result<T>
 where
     F: FnOnce(&str) -> Result<T>,
@@ -129,16 +740,273 @@ fn optional_env<T, F>(key: &str, parser: F) -> Result<Option<T>>
 where
     F: FnOnce(&str) -> Result<T>,
 {
+    optional_var(&process_env, key, parser)
+}
+
+/// Parses `key` from `env`, treating an unset or blank value as absent.
+fn optional_var<T, F>(env: &EnvLookup, key: &str, parser: F) -> Result<Option<T>>
+where
+    F: FnOnce(&str) -> Result<T>,
+{
+    match env(key)? {
+        Some(raw) if !raw.trim().is_empty() => parser(raw.trim()).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn process_env(key: &str) -> Result<Option<String>> {
     match dotenvy::var(key) {
-        Ok(raw) => {
-            let value = raw.trim();
-            if value.is_empty() {
-                Ok(None)
-            } else {
-                parser(value).map(Some)
-            }
-        }
+        Ok(raw) => Ok(Some(raw)),
         Err(dotenvy::Error::EnvVar(VarError::NotPresent)) => Ok(None),
         Err(err) => Err(err.into()),
     }
 }
+
+fn parse_remote_addresses(value: &str) -> Result<Vec<Address>> {
+    value
+        .split(',')
+        .map(|part| {
+            Address::parse_checksummed(part.trim(), None).map_err(|_| {
+                eyre!("REMOTE_SIGNER_ADDRESSES entry {part} is not a valid checksummed address")
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BidFile, SignerSource, keystore_password, parse_remote_addresses, senders_from_keystore,
+        signer_source,
+    };
+    use crate::transaction::AccessListConfig;
+    use alloy::signers::local::LocalSigner;
+    use eyre::Result;
+    use std::{collections::HashMap, fs, path::Path};
+
+    /// An environment holding only `vars`.
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Result<Option<String>> + 'a {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        move |key| Ok(vars.get(key).map(|value| value.to_string()))
+    }
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("cca-config-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn merges_global_and_per_bid_tx_sections() {
+        let file: BidFile = toml::from_str(
+            r#"
+            [tx]
+            max_fee = "40 gwei"
+            priority_fee = "2 gwei"
+            max_retries = 5
+            hint_fallback = true
+
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+            tx = { priority_fee = "3 gwei", access_list = "generate" }
+            "#,
+        )
+        .unwrap();
+        let global = file.tx.unwrap_or_default();
+        let mut bids = file.bids.into_iter();
+
+        let first = bids.next().unwrap().tx.unwrap_or_default().or(&global);
+        let first = first.into_settings("bid #1").unwrap();
+        let fees = first.tx_config.unwrap().fees.unwrap();
+        assert_eq!(fees.max_fee_per_gas, 40_000_000_000);
+        assert_eq!(fees.max_priority_fee_per_gas, 2_000_000_000);
+        assert_eq!(first.max_retries, Some(5));
+        assert!(first.hint_fallback);
+
+        let second = bids.next().unwrap().tx.unwrap_or_default().or(&global);
+        let second = second.into_settings("bid #2").unwrap().tx_config.unwrap();
+        assert_eq!(second.fees.unwrap().max_priority_fee_per_gas, 3_000_000_000);
+        assert!(matches!(second.access_list, AccessListConfig::Generate));
+    }
+
+    #[test]
+    fn per_bid_access_list_mode_replaces_global_entries() {
+        let file: BidFile = toml::from_str(
+            r#"
+            [tx]
+            access_list = "provided"
+            access_list_entries = [{ address = "0x1111111111111111111111111111111111111111", storageKeys = [] }]
+
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+            tx = { access_list = "generate" }
+
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+            tx = { access_list = "none" }
+            "#,
+        )
+        .unwrap();
+        let global = file.tx.unwrap_or_default();
+        let settings: Vec<_> = file
+            .bids
+            .into_iter()
+            .enumerate()
+            .map(|(idx, bid)| {
+                bid.tx
+                    .unwrap_or_default()
+                    .or(&global)
+                    .into_settings(&format!("bid #{}", idx + 1))
+                    .unwrap()
+            })
+            .collect();
+
+        let inherited = settings[0].tx_config.as_ref().unwrap();
+        assert!(
+            matches!(&inherited.access_list, AccessListConfig::Provided(list) if list.0.len() == 1)
+        );
+        let generated = settings[1].tx_config.as_ref().unwrap();
+        assert!(matches!(generated.access_list, AccessListConfig::Generate));
+        assert!(settings[2].tx_config.is_none());
+    }
+
+    #[test]
+    fn remote_signer_addresses_must_be_checksummed() {
+        let addresses = parse_remote_addresses(
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D, 0xde709f2102306220921060314715629080e2fb77",
+        )
+        .unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert!(parse_remote_addresses("0x8617e340B3D01FA5F11F306F4090FD50E238070D").is_err());
+    }
+
+    #[test]
+    fn rejects_partial_fee_overrides() {
+        let file: BidFile = toml::from_str(
+            r#"
+            [[bids]]
+            max_bid = "1"
+            amount = "1"
+            tx = { max_fee = "40 gwei" }
+            "#,
+        )
+        .unwrap();
+        let bid = file.bids.into_iter().next().unwrap();
+        assert!(bid.tx.unwrap().into_settings("bid #1").is_err());
+    }
+
+    #[test]
+    fn plaintext_secrets_need_the_opt_in() {
+        const KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        for key in ["PRIVATE_KEY", "MNEMONIC"] {
+            let err = signer_source(&env(&[(key, KEY)]), None).err().unwrap();
+            assert!(err.to_string().contains("ALLOW_PLAINTEXT_KEY=true"));
+            let err = signer_source(&env(&[(key, KEY), ("ALLOW_PLAINTEXT_KEY", "false")]), None)
+                .err()
+                .unwrap();
+            assert!(err.to_string().contains("ALLOW_PLAINTEXT_KEY=true"));
+        }
+
+        let allowed = [("PRIVATE_KEY", KEY), ("ALLOW_PLAINTEXT_KEY", "true")];
+        assert!(matches!(
+            signer_source(&env(&allowed), None),
+            Ok(SignerSource::PrivateKey(key)) if key == KEY
+        ));
+        let allowed = [("MNEMONIC", "test phrase"), ("ALLOW_PLAINTEXT_KEY", "true")];
+        assert!(matches!(
+            signer_source(&env(&allowed), None),
+            Ok(SignerSource::Mnemonic(_))
+        ));
+    }
+
+    #[test]
+    fn signer_sources_are_exclusive() {
+        let both = [
+            ("KEYSTORE_PATH", "key.json"),
+            ("REMOTE_SIGNER_URL", "http://localhost:8550"),
+        ];
+        let err = signer_source(&env(&both), None).err().unwrap();
+        assert!(err.to_string().contains("set only one of"));
+
+        let cli_and_env = [("MNEMONIC_FILE", "phrase.txt")];
+        assert!(signer_source(&env(&cli_and_env), Some(Path::new("key.json"))).is_err());
+
+        let mnemonics = [("MNEMONIC_FILE", "phrase.txt"), ("MNEMONIC", "test phrase")];
+        let err = signer_source(&env(&mnemonics), None).err().unwrap();
+        assert!(err.to_string().contains("both MNEMONIC_FILE and MNEMONIC"));
+
+        // A blank variable does not count as a source.
+        let blank = [("KEYSTORE_PATH", "key.json"), ("PRIVATE_KEY", "  ")];
+        assert!(matches!(
+            signer_source(&env(&blank), None),
+            Ok(SignerSource::Keystore(path)) if path == Path::new("key.json")
+        ));
+        assert!(signer_source(&env(&[]), None).is_err());
+    }
+
+    #[test]
+    fn keystore_password_prefers_the_file() {
+        let dir = scratch_dir("password");
+        let file = dir.join("password.txt");
+        fs::write(&file, "from file\r\n").unwrap();
+        let file = file.to_str().unwrap();
+
+        let both = [
+            ("KEYSTORE_PASSWORD_FILE", file),
+            ("KEYSTORE_PASSWORD", "from env"),
+        ];
+        assert_eq!(
+            keystore_password(&env(&both)).unwrap().as_deref(),
+            Some("from file")
+        );
+        assert_eq!(
+            keystore_password(&env(&[("KEYSTORE_PASSWORD", "from env")]))
+                .unwrap()
+                .as_deref(),
+            Some("from env")
+        );
+        // Surrounding spaces are kept, only a trailing newline is dropped.
+        assert_eq!(
+            keystore_password(&env(&[("KEYSTORE_PASSWORD", " from env \n")]))
+                .unwrap()
+                .as_deref(),
+            Some(" from env ")
+        );
+        // An empty password variable falls through to the prompt.
+        assert_eq!(
+            keystore_password(&env(&[("KEYSTORE_PASSWORD", "")])).unwrap(),
+            None
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn decrypts_a_keystore_with_its_password() {
+        let dir = scratch_dir("keystore");
+        let (signer, _uuid) = LocalSigner::encrypt_keystore(
+            &dir,
+            &mut rand::thread_rng(),
+            [0x11u8; 32],
+            "hunter2",
+            Some("key.json"),
+        )
+        .unwrap();
+
+        let path = dir.join("key.json");
+        let senders = senders_from_keystore(&path, "hunter2".to_string()).unwrap();
+        assert_eq!(senders.addresses(), [signer.address()]);
+        assert!(senders_from_keystore(&path, "wrong".to_string()).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{CCA::CCAErrors, ValidationHook::ValidationHookErrors};
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    primitives::{B256, Bytes},
+    sol_types::{ContractError, SolInterface},
+    transports::TransportError,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How the pipeline reacts to a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureClass {
+    /// May succeed in a later block; spends one retry.
+    Retryable,
+    /// Will fail again however often it is resent; the bid is closed.
+    Fatal,
+    /// The `prevTickPrice` hint was rejected; recomputed without spending a retry.
+    Hint,
+}
+
+impl fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Retryable => "retryable",
+            Self::Fatal => "fatal",
+            Self::Hint => "hint",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A failed bid attempt, decoded against the CCA and validation hook errors.
+#[derive(Debug, Clone)]
+pub enum BidError {
+    Auction(CCAErrors),
+    Hook(ValidationHookErrors),
+    /// `Error(string)` or a panic.
+    Revert(String),
+    /// Revert data that matches no declared error.
+    UnknownRevert(Bytes),
+    /// Not a revert: transport, signing or receipt errors.
+    Other,
+}
+
+impl BidError {
+    pub fn from_report(err: &eyre::Report) -> Self {
+        err.chain()
+            .find_map(|cause| {
+                if let Some(RevertData(data)) = cause.downcast_ref::<RevertData>() {
+                    return Some(data.clone());
+                }
+                cause
+                    .downcast_ref::<TransportError>()
+                    .and_then(TransportError::as_error_resp)
+                    .and_then(|payload| payload.as_revert_data())
+            })
+            .map_or(Self::Other, |data| Self::from_revert_data(&data))
+    }
+
+    pub fn from_revert_data(data: &[u8]) -> Self {
+        match ContractError::<CCAErrors>::abi_decode(data) {
+            Ok(ContractError::CustomError(CCAErrors::ValidationHookCallFailed(failed))) => {
+                match ValidationHookErrors::abi_decode(&failed.reason) {
+                    Ok(err) => Self::Hook(err),
+                    Err(_) => Self::Auction(CCAErrors::ValidationHookCallFailed(failed)),
+                }
+            }
+            Ok(ContractError::CustomError(err)) => Self::Auction(err),
+            Ok(ContractError::Revert(revert)) => Self::Revert(revert.reason),
+            Ok(ContractError::Panic(panic)) => Self::Revert(panic.to_string()),
+            Err(_) => match ValidationHookErrors::abi_decode(data) {
+                Ok(err) => Self::Hook(err),
+                Err(_) => Self::UnknownRevert(Bytes::copy_from_slice(data)),
+            },
+        }
+    }
+
+    pub fn class(&self) -> FailureClass {
+        match self {
+            Self::Auction(
+                CCAErrors::TickPreviousPriceInvalid(_) | CCAErrors::TickPriceNotIncreasing(_),
+            ) => FailureClass::Hint,
+            Self::Auction(CCAErrors::AuctionNotStarted(_) | CCAErrors::TokensNotReceived(_))
+            | Self::Hook(ValidationHookErrors::ContributorPeriodNotEnded(_)) => {
+                FailureClass::Retryable
+            }
+            Self::Auction(_) | Self::Hook(_) => FailureClass::Fatal,
+            Self::Revert(_) | Self::UnknownRevert(_) | Self::Other => FailureClass::Retryable,
+        }
+    }
+
+    /// Name of the decoded custom error.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Auction(err) => CCAErrors::name_by_selector(err.selector()),
+            Self::Hook(err) => ValidationHookErrors::name_by_selector(err.selector()),
+            _ => None,
+        }
+    }
+
+    /// Arguments of the decoded custom error, formatted in declaration order.
+    pub fn args(&self) -> Vec<String> {
+        let (signature, encoded) = match self {
+            Self::Auction(err) => (
+                CCAErrors::signature_by_selector(err.selector()),
+                err.abi_encode(),
+            ),
+            Self::Hook(err) => (
+                ValidationHookErrors::signature_by_selector(err.selector()),
+                err.abi_encode(),
+            ),
+            Self::Revert(reason) => return vec![reason.clone()],
+            Self::UnknownRevert(data) => return vec![data.to_string()],
+            Self::Other => return Vec::new(),
+        };
+        signature
+            .and_then(|signature| decode_args(signature, &encoded[4..]))
+            .unwrap_or_default()
+    }
+}
+
+/// Revert data of an included transaction, recovered from its trace.
+#[derive(Debug, Clone)]
+pub struct RevertData(pub Bytes);
+
+impl fmt::Display for RevertData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "execution reverted: {}", self.0)
+    }
+}
+
+impl std::error::Error for RevertData {}
+
+/// A transaction that was included and succeeded but carries no `BidSubmitted`
+/// event. Its nonce is spent and it may hold a bid, so it must not be resent.
+#[derive(Debug, Clone, Copy)]
+pub struct UnconfirmedBid(pub B256);
+
+impl fmt::Display for UnconfirmedBid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bid transaction {} succeeded but emitted no BidSubmitted event",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnconfirmedBid {}
+
+/// Decodes the parameters of `Name(type,...)` from ABI-encoded `data`.
+fn decode_args(signature: &str, data: &[u8]) -> Option<Vec<String>> {
+    let params = &signature[signature.find('(')?..];
+    let DynSolValue::Tuple(values) = DynSolType::parse(params)
+        .ok()?
+        .abi_decode_params(data)
+        .ok()?
+    else {
+        return None;
+    };
+    Some(values.iter().map(format_value).collect())
+}
+
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::Address(value) => value.to_string(),
+        DynSolValue::FixedBytes(value, size) => Bytes::copy_from_slice(&value[..*size]).to_string(),
+        DynSolValue::Bytes(value) => Bytes::copy_from_slice(value).to_string(),
+        DynSolValue::String(value) => value.clone(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// A failed attempt as recorded in the bid state and the summary file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidFailure {
+    pub class: FailureClass,
+    /// Decoded custom error, e.g. `BidMustBeAboveClearingPrice`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    pub message: String,
+}
+
+impl BidFailure {
+    pub fn from_report(err: &eyre::Report) -> Self {
+        let decoded = BidError::from_report(err);
+        Self {
+            class: decoded.class(),
+            name: decoded.name().map(str::to_string),
+            args: decoded.args(),
+            message: format!("{err:#}"),
+        }
+    }
+
+    /// A pending bid that the latest auction state rules out before sending.
+    pub fn invalidated(reason: String) -> Self {
+        Self {
+            class: FailureClass::Fatal,
+            name: None,
+            args: Vec::new(),
+            message: reason,
+        }
+    }
+}
+
+impl fmt::Display for BidFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name}({}) [{}]", self.args.join(", "), self.class),
+            None => write!(f, "{} [{}]", self.message, self.class),
+        }
+    }
+}
+
+/// Hash of the included transaction if `err` is an [`UnconfirmedBid`].
+pub fn unconfirmed_tx(err: &eyre::Report) -> Option<B256> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<UnconfirmedBid>())
+        .map(|unconfirmed| unconfirmed.0)
+}
+
+/// True if the CCA rejected the bid's `prevTickPrice`.
+pub fn is_stale_hint(err: &eyre::Report) -> bool {
+    BidError::from_report(err).class() == FailureClass::Hint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BidError, BidFailure, FailureClass, is_stale_hint};
+    use crate::{CCA, ValidationHook};
+    use alloy::{
+        primitives::{Address, Bytes, U256},
+        sol_types::{Revert, SolError},
+        transports::TransportError,
+    };
+    use eyre::WrapErr;
+    use serde_json::json;
+
+    fn revert(data: Vec<u8>) -> eyre::Report {
+        let payload = serde_json::from_value(json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": Bytes::from(data),
+        }))
+        .unwrap();
+        let error: TransportError = TransportError::err_resp(payload);
+        eyre::Report::new(error)
+    }
+
+    #[test]
+    fn recognises_stale_hint_reverts() {
+        let stale = revert(CCA::TickPreviousPriceInvalid {}.abi_encode());
+        assert!(is_stale_hint(&stale));
+        let wrapped = Err::<(), _>(stale)
+            .wrap_err("simulation failed")
+            .unwrap_err();
+        assert!(is_stale_hint(&wrapped));
+
+        assert!(!is_stale_hint(&revert(vec![0xde, 0xad, 0xbe, 0xef])));
+        assert!(!is_stale_hint(&eyre::eyre!("nonce too low")));
+    }
+
+    #[test]
+    fn decodes_auction_and_hook_errors_with_arguments() {
+        let too_high = revert(
+            CCA::InvalidBidPriceTooHigh {
+                maxPrice: U256::from(7u64),
+                maxBidPrice: U256::from(5u64),
+            }
+            .abi_encode(),
+        );
+        let failure = BidFailure::from_report(&too_high);
+        assert_eq!(failure.class, FailureClass::Fatal);
+        assert_eq!(failure.name.as_deref(), Some("InvalidBidPriceTooHigh"));
+        assert_eq!(failure.args, ["7", "5"]);
+        assert_eq!(failure.to_string(), "InvalidBidPriceTooHigh(7, 5) [fatal]");
+
+        let sender = Address::repeat_byte(0x22);
+        let hook_error = ValidationHook::PurchaseLimitExceeded {
+            sender,
+            totalPurchased: U256::from(10u64),
+            maxPurchaseLimit: U256::from(8u64),
+        }
+        .abi_encode();
+        let wrapped = CCA::ValidationHookCallFailed {
+            reason: hook_error.into(),
+        }
+        .abi_encode();
+        let failure = BidFailure::from_report(&revert(wrapped));
+        assert_eq!(failure.class, FailureClass::Fatal);
+        assert_eq!(failure.name.as_deref(), Some("PurchaseLimitExceeded"));
+        assert_eq!(failure.args, [sender.to_string(), "10".into(), "8".into()]);
+
+        let not_started = BidError::from_revert_data(&CCA::AuctionNotStarted {}.abi_encode());
+        assert_eq!(not_started.class(), FailureClass::Retryable);
+    }
+
+    #[test]
+    fn falls_back_for_reason_strings_and_unknown_data() {
+        let reason = Revert::from("paused").abi_encode();
+        let failure = BidFailure::from_report(&revert(reason));
+        assert_eq!(failure.class, FailureClass::Retryable);
+        assert_eq!(failure.name, None);
+        assert_eq!(failure.args, ["paused"]);
+
+        let unknown = BidError::from_revert_data(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches!(unknown, BidError::UnknownRevert(_)));
+        assert_eq!(
+            BidFailure::from_report(&eyre::eyre!("connection reset")).class,
+            FailureClass::Retryable
+        );
+    }
+}
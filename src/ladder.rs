@@ -0,0 +1,376 @@
+use crate::{
+    auction::AuctionParams,
+    bids::{TickRounding, align_price_to_tick},
+    config::{BidParams, TxSettings},
+    schedule::WindowSpec,
+    units::{AmountSpec, AmountUnit, Decimal},
+};
+use alloy::primitives::{Address, U256};
+use eyre::{Result, eyre};
+use serde::Deserialize;
+
+/// Fixed-point precision used for geometric step factors.
+const GEOMETRIC_SCALE: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LadderSpacing {
+    Linear,
+    Geometric,
+    Ticks,
+}
+
+/// How a ladder's total amount is spread over its price levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LadderWeighting {
+    #[default]
+    Flat,
+    /// Largest share at the lowest price, shrinking towards the top.
+    Front,
+    /// Largest share at the highest price, shrinking towards the bottom.
+    Back,
+}
+
+/// A `[[ladders]]` entry that expands into one bid per price level.
+#[derive(Debug, Clone)]
+pub struct LadderTemplate {
+    pub label: String,
+    pub min_price: Decimal,
+    pub max_price: Decimal,
+    pub steps: Option<usize>,
+    pub spacing: LadderSpacing,
+    pub every_ticks: Option<u64>,
+    pub amount: AmountSpec,
+    pub amount_unit: AmountUnit,
+    pub weighting: LadderWeighting,
+    pub owner: Address,
+    pub sender: Address,
+    pub tx: TxSettings,
+    pub window: WindowSpec,
+    pub rounding: TickRounding,
+}
+
+impl LadderTemplate {
+    pub fn expand(&self, params: &AuctionParams) -> Result<Vec<BidParams>> {
+        let label = &self.label;
+        let scale = params.price_scale;
+        let min = scale
+            .to_q96(&self.min_price)
+            .map_err(|err| eyre!("ladder {label} min_price is invalid: {err}"))?;
+        let max = scale
+            .to_q96(&self.max_price)
+            .map_err(|err| eyre!("ladder {label} max_price is invalid: {err}"))?;
+        if min > max {
+            return Err(eyre!(
+                "ladder {label} min_price {} is above max_price {}",
+                self.min_price,
+                self.max_price
+            ));
+        }
+
+        let raw_levels = match self.spacing {
+            LadderSpacing::Linear => linear_levels(min, max, self.required_steps()?),
+            LadderSpacing::Geometric => geometric_levels(min, max, self.required_steps()?)
+                .map_err(|err| eyre!("ladder {label}: {err}"))?,
+            LadderSpacing::Ticks => {
+                let every = self.every_ticks.filter(|every| *every > 0).ok_or_else(|| {
+                    eyre!("ladder {label} spacing = \"ticks\" requires every_ticks >= 1")
+                })?;
+                tick_levels(min, max, every, self.required_steps()?, params)
+                    .map_err(|err| eyre!("ladder {label}: {err}"))?
+            }
+        };
+
+        let levels = raw_levels
+            .into_iter()
+            .map(|requested| {
+                align_price_to_tick(requested, params, self.rounding)
+                    .map(|max_bid| (requested, max_bid))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(|err| eyre!("ladder {label}: {err}"))?;
+        let distinct = 1 + levels
+            .windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .count();
+        if distinct < levels.len() {
+            return Err(eyre!(
+                "ladder {label} has {} levels but only {distinct} distinct ticks between min_price and max_price, lower steps or widen the range",
+                levels.len()
+            ));
+        }
+
+        let total = self
+            .amount
+            .to_base_units(&self.amount_unit, &params.currency)
+            .map_err(|err| eyre!("ladder {label} amount {} is invalid: {err}", self.amount))?;
+        let amounts = split_amount(total, levels.len(), self.weighting);
+        if amounts.contains(&0) {
+            return Err(eyre!(
+                "ladder {label} amount {} is too small to spread over {} levels",
+                self.amount,
+                levels.len()
+            ));
+        }
+
+        let window = self
+            .window
+            .resolve(params)
+            .map_err(|err| eyre!("ladder {label} block window is invalid: {err}"))?;
+
+        Ok(levels
+            .into_iter()
+            .zip(amounts)
+            .enumerate()
+            .map(|(idx, ((requested_max_bid, max_bid), amount))| BidParams {
+                label: format!("{label}-{}", idx + 1),
+                max_bid,
+                requested_max_bid,
+                amount,
+                owner: self.owner,
+                sender: self.sender,
+                tx: self.tx.clone(),
+                window,
+                rounding: self.rounding,
+            })
+            .collect())
+    }
+
+    fn required_steps(&self) -> Result<usize> {
+        match self.steps {
+            Some(steps) if steps >= 1 => Ok(steps),
+            _ => Err(eyre!("ladder {} requires steps >= 1", self.label)),
+        }
+    }
+}
+
+fn linear_levels(min: U256, max: U256, steps: usize) -> Vec<U256> {
+    if steps == 1 {
+        return vec![min];
+    }
+    let intervals = U256::from(steps - 1);
+    (0..steps)
+        .map(|idx| min + (max - min) * U256::from(idx) / intervals)
+        .collect()
+}
+
+/// The step factor is computed in `f64`, so each level carries a relative
+/// error around 1e-15. That is far below one tick, and the top level is pinned
+/// to `max` exactly.
+fn geometric_levels(min: U256, max: U256, steps: usize) -> Result<Vec<U256>> {
+    if steps == 1 {
+        return Ok(vec![min]);
+    }
+    if min.is_zero() {
+        return Err(eyre!("geometric spacing needs a min_price above zero"));
+    }
+    let ratio = f64::from(max) / f64::from(min);
+    let exponent = 1.0 / (steps - 1) as f64;
+    (0..steps)
+        .map(|idx| {
+            if idx == steps - 1 {
+                return Ok(max);
+            }
+            let factor = ratio.powf(exponent * idx as f64) * GEOMETRIC_SCALE as f64;
+            if !factor.is_finite() || factor >= u128::MAX as f64 {
+                return Err(eyre!("price range is too wide for geometric spacing"));
+            }
+            Ok(min * U256::from(factor as u128) / U256::from(GEOMETRIC_SCALE))
+        })
+        .collect()
+}
+
+fn tick_levels(
+    min: U256,
+    max: U256,
+    every: u64,
+    steps: usize,
+    params: &AuctionParams,
+) -> Result<Vec<U256>> {
+    if params.tick_spacing.is_zero() {
+        return Err(eyre!(
+            "auction tick spacing is zero, so spacing = \"ticks\" cannot step"
+        ));
+    }
+    let stride = params.tick_spacing * U256::from(every);
+    let mut levels = Vec::new();
+    // Start on the first tick at or above min_price so every level stays in range.
+    let mut price = align_price_to_tick(min, params, TickRounding::Up)?;
+    while price <= max && levels.len() < steps {
+        levels.push(price);
+        price += stride;
+    }
+    if levels.len() < steps {
+        return Err(eyre!(
+            "only {} levels every {every} ticks fit between min_price and max_price, fewer than steps = {steps}, lower steps or widen the range",
+            levels.len()
+        ));
+    }
+    Ok(levels)
+}
+
+fn split_amount(total: u128, levels: usize, weighting: LadderWeighting) -> Vec<u128> {
+    let weights: Vec<u128> = (0..levels)
+        .map(|idx| match weighting {
+            LadderWeighting::Flat => 1,
+            LadderWeighting::Front => (levels - idx) as u128,
+            LadderWeighting::Back => (idx + 1) as u128,
+        })
+        .collect();
+    let weight_sum: u128 = weights.iter().sum();
+
+    let mut amounts: Vec<u128> = weights
+        .iter()
+        .map(|weight| (U256::from(total) * U256::from(*weight) / U256::from(weight_sum)).to())
+        .collect();
+
+    // Rounding dust goes to the most heavily weighted level.
+    let assigned: u128 = amounts.iter().sum();
+    let heaviest = match weighting {
+        LadderWeighting::Back => levels - 1,
+        LadderWeighting::Flat | LadderWeighting::Front => 0,
+    };
+    if let Some(amount) = amounts.get_mut(heaviest) {
+        *amount += total - assigned;
+    }
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LadderSpacing, LadderTemplate, LadderWeighting, split_amount};
+    use crate::{
+        auction::AuctionParams,
+        bids::TickRounding,
+        config::TxSettings,
+        schedule::WindowSpec,
+        units::{AmountSpec, AmountUnit},
+    };
+    use alloy::primitives::{Address, U256};
+    use std::str::FromStr;
+
+    fn params() -> AuctionParams {
+        AuctionParams::for_test()
+            .with_ticks(U256::from(1_000u64), U256::from(100u64))
+            .with_max_bid_price(U256::from(100_000_000u64))
+    }
+
+    fn ladder(spacing: LadderSpacing, steps: Option<usize>) -> LadderTemplate {
+        // With 18/18 decimals, a decimal price p maps to p * 2^96 in Q96; these
+        // prices are tiny so the tick grid above dominates.
+        LadderTemplate {
+            label: "ladder".to_string(),
+            min_price: "0.0000000000000000000000015".parse().unwrap(),
+            max_price: "0.00000000000000000000006".parse().unwrap(),
+            steps,
+            spacing,
+            every_ticks: Some(5),
+            amount: AmountSpec::from_str("1000").unwrap(),
+            amount_unit: AmountUnit::Wei,
+            weighting: LadderWeighting::Flat,
+            owner: Address::ZERO,
+            sender: Address::ZERO,
+            tx: TxSettings::default(),
+            window: WindowSpec::default(),
+            rounding: TickRounding::Down,
+        }
+    }
+
+    #[test]
+    fn splits_amounts_exactly() {
+        assert_eq!(split_amount(10, 3, LadderWeighting::Flat), vec![4, 3, 3]);
+        assert_eq!(
+            split_amount(60, 3, LadderWeighting::Front),
+            vec![30, 20, 10]
+        );
+        assert_eq!(split_amount(61, 3, LadderWeighting::Back), vec![10, 20, 31]);
+    }
+
+    #[test]
+    fn expands_linear_ladder_onto_ticks() {
+        let params = params();
+        let bids = ladder(LadderSpacing::Linear, Some(4))
+            .expand(&params)
+            .unwrap();
+        assert_eq!(bids.len(), 4);
+        assert_eq!(bids[0].label, "ladder-1");
+        assert_eq!(bids.iter().map(|bid| bid.amount).sum::<u128>(), 1000);
+
+        // Levels are snapped to ticks and keep the price they were asked at.
+        assert!(bids.iter().any(|bid| bid.requested_max_bid != bid.max_bid));
+        for bid in &bids {
+            assert!(bid.max_bid <= bid.requested_max_bid);
+            let offset = bid.max_bid - params.floor_price;
+            assert!((offset % params.tick_spacing).is_zero());
+        }
+        assert!(
+            bids.windows(2)
+                .all(|pair| pair[0].max_bid < pair[1].max_bid)
+        );
+    }
+
+    #[test]
+    fn expands_geometric_ladder_within_range() {
+        let params = params();
+        let bids = ladder(LadderSpacing::Geometric, Some(5))
+            .expand(&params)
+            .unwrap();
+        assert!(bids.len() > 1);
+        assert!(
+            bids.windows(2)
+                .all(|pair| pair[0].max_bid < pair[1].max_bid)
+        );
+        let gaps: Vec<U256> = bids
+            .windows(2)
+            .map(|pair| pair[1].max_bid - pair[0].max_bid)
+            .collect();
+        assert!(gaps.first() < gaps.last());
+    }
+
+    #[test]
+    fn expands_every_n_ticks() {
+        let params = params();
+        let bids = ladder(LadderSpacing::Ticks, Some(3))
+            .expand(&params)
+            .unwrap();
+        let stride = params.tick_spacing * U256::from(5u64);
+        assert_eq!(bids.len(), 3);
+        assert_eq!(bids[1].max_bid - bids[0].max_bid, stride);
+        assert_eq!(bids[2].max_bid - bids[1].max_bid, stride);
+    }
+
+    #[test]
+    fn rejects_levels_that_collapse_onto_one_tick() {
+        let params = params();
+        let mut narrow = ladder(LadderSpacing::Linear, Some(4));
+        narrow.max_price = narrow.min_price;
+        let err = narrow.expand(&params).unwrap_err();
+        assert!(err.to_string().contains("only 1 distinct ticks"));
+    }
+
+    #[test]
+    fn rejects_tick_ladders_with_too_few_ticks_in_range() {
+        let mut narrow = ladder(LadderSpacing::Ticks, Some(3));
+        narrow.max_price = narrow.min_price;
+        let err = narrow.expand(&params()).unwrap_err();
+        assert!(err.to_string().contains("fewer than steps = 3"));
+    }
+
+    #[test]
+    fn rejects_zero_tick_spacing() {
+        let flat = params().with_ticks(U256::from(1_000u64), U256::ZERO);
+        let err = ladder(LadderSpacing::Linear, Some(4))
+            .expand(&flat)
+            .unwrap_err();
+        assert!(err.to_string().contains("tick spacing is zero"));
+    }
+
+    #[test]
+    fn tick_spacing_requires_steps() {
+        let err = ladder(LadderSpacing::Ticks, None)
+            .expand(&params())
+            .unwrap_err();
+        assert!(err.to_string().contains("requires steps"));
+    }
+}
@@ -3,10 +3,10 @@ use crate::{
     registry::{BidOutcomeState, BidSummary},
 };
 use eyre::{Result, WrapErr};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::{error, info, warn};
@@ -24,36 +24,42 @@ pub fn log_summary(summary: &BidSummary, reason: &ShutdownReason) {
         ShutdownReason::AllBidsProcessed => info!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary"
         ),
         ShutdownReason::AuctionEndedWithPending => warn!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary (auction ended early)"
         ),
         ShutdownReason::BlockStreamError => error!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary (block stream error)"
         ),
         ShutdownReason::BlockStreamErrorWithPending => error!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary (block stream error with pending bids)"
         ),
         ShutdownReason::BlockStreamEnded => warn!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary (block stream ended)"
         ),
         ShutdownReason::BlockStreamEndedWithPending => warn!(
             submitted = summary.submitted,
             failed = summary.failed,
+            expired = summary.expired,
             pending = summary.pending,
             "bid summary (block stream ended with pending bids)"
         ),
@@ -61,24 +67,72 @@ pub fn log_summary(summary: &BidSummary, reason: &ShutdownReason) {
 
     for outcome in &summary.outcomes {
         match &outcome.state {
-            BidOutcomeState::Submitted { tx_hash } => info!(
+            BidOutcomeState::Submitted {
+                tx_hash,
+                bid_id,
+                mismatch: None,
+            } => info!(
+                label = %outcome.label,
                 owner = ?outcome.owner,
+                sender = ?outcome.sender,
                 amount = outcome.amount,
                 tx_hash = ?tx_hash,
+                bid_id = ?bid_id,
                 "bid submitted"
             ),
-            BidOutcomeState::Failed { error } => warn!(
+            BidOutcomeState::Submitted {
+                tx_hash,
+                bid_id,
+                mismatch: Some(mismatch),
+            } => error!(
+                label = %outcome.label,
+                owner = ?outcome.owner,
+                sender = ?outcome.sender,
+                amount = outcome.amount,
+                tx_hash = ?tx_hash,
+                bid_id = ?bid_id,
+                mismatch,
+                "bid submitted but differs from the plan"
+            ),
+            BidOutcomeState::Unconfirmed { tx_hash, error } => error!(
+                label = %outcome.label,
+                owner = ?outcome.owner,
+                sender = ?outcome.sender,
+                amount = outcome.amount,
+                tx_hash = ?tx_hash,
+                error,
+                "bid included but unconfirmed"
+            ),
+            BidOutcomeState::Failed { error, .. } => warn!(
+                label = %outcome.label,
                 owner = ?outcome.owner,
+                sender = ?outcome.sender,
                 amount = outcome.amount,
                 error,
                 "bid failed"
             ),
+            BidOutcomeState::Expired {
+                block,
+                attempts,
+                last_error,
+            } => warn!(
+                label = %outcome.label,
+                owner = ?outcome.owner,
+                sender = ?outcome.sender,
+                amount = outcome.amount,
+                block = %block,
+                attempts,
+                last_error = ?last_error,
+                "bid expired"
+            ),
             BidOutcomeState::Pending {
                 attempts,
                 max_retries,
                 last_error,
             } => info!(
+                label = %outcome.label,
                 owner = ?outcome.owner,
+                sender = ?outcome.sender,
                 amount = outcome.amount,
                 attempts,
                 max_retries,
@@ -89,10 +143,10 @@ pub fn log_summary(summary: &BidSummary, reason: &ShutdownReason) {
     }
 }
 
-#[derive(Serialize)]
-struct PersistedSummary {
-    reason: ShutdownReason,
-    summary: BidSummary,
+#[derive(Serialize, Deserialize)]
+pub struct PersistedSummary {
+    pub reason: ShutdownReason,
+    pub summary: BidSummary,
 }
 
 pub fn persist_summary(summary: &BidSummary, reason: &ShutdownReason) -> Result<PathBuf> {
@@ -109,3 +163,9 @@ pub fn persist_summary(summary: &BidSummary, reason: &ShutdownReason) -> Result<
     serde_json::to_writer_pretty(&mut file, &payload).wrap_err("failed to write summary file")?;
     Ok(path)
 }
+
+pub fn load_summary(path: &Path) -> Result<PersistedSummary> {
+    let file =
+        File::open(path).wrap_err(format!("failed to open summary file at {}", path.display()))?;
+    serde_json::from_reader(file).wrap_err("failed to parse summary file (expected JSON)")
+}
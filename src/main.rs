@@ -2,14 +2,27 @@ mod app;
 mod auction;
 mod bids;
 mod blocks;
+mod cli;
 mod config;
+mod failure;
+mod ladder;
 mod logging;
+mod network;
+mod offline;
 mod registry;
+mod report;
+mod safe;
+mod schedule;
+mod senders;
+mod signer;
+mod ticks;
 mod transaction;
+mod units;
 mod validate;
 
-use crate::{app::AuctionBot, config::Config, logging::init_logging};
-use alloy::{providers::ProviderBuilder, sol};
+use crate::{cli::Cli, logging::init_logging};
+use alloy::sol;
+use clap::Parser;
 use eyre::Result;
 
 sol! {
@@ -21,11 +34,63 @@ sol! {
             uint256 currencyDemandQ96;
         }
 
+        function token() external view returns (address);
+        function currency() external view returns (address);
+        function validationHook() external view returns (address);
         function floorPrice() external view returns (uint256);
         function tickSpacing() external view returns (uint256);
         function MAX_BID_PRICE() external view returns (uint256);
+        function clearingPrice() external view returns (uint256);
+        function currencyRaised() external view returns (uint256);
+        function totalSupply() external view returns (uint128);
+
+        struct Checkpoint {
+            uint256 clearingPrice;
+            uint256 currencyRaisedAtClearingPriceQ96_X7;
+            uint256 cumulativeMpsPerPrice;
+            uint24 cumulativeMps;
+            uint64 prev;
+            uint64 next;
+        }
+
+        function latestCheckpoint() external view returns (Checkpoint memory checkpoint);
+        function lastCheckpointedBlock() external view returns (uint64);
+        function startBlock() external view returns (uint64);
         function endBlock() external view returns (uint64);
+        function claimBlock() external view returns (uint64);
+        event TickInitialized(uint256 price);
+        event BidSubmitted(uint256 indexed id, address indexed owner, uint256 price, uint128 amount);
+
+        error AuctionNotStarted();
+        error AuctionIsOver();
+        error AuctionSoldOut();
+        error TokensNotReceived();
+        error InvalidAmount();
+        error BidAmountTooSmall();
+        error BidOwnerCannotBeZeroAddress();
+        error BidMustBeAboveClearingPrice();
+        error InvalidBidPriceTooHigh(uint256 maxPrice, uint256 maxBidPrice);
+        error InvalidBidUnableToClear();
+        error CurrencyIsNotNative();
+        error InvalidTickPrice();
+        error TickPriceNotAtBoundary();
+        error TickPriceNotIncreasing();
+        error TickPreviousPriceInvalid();
+        error ValidationHookCallFailed(bytes reason);
+
         function ticks(uint256 price) external view returns (Tick memory tick);
+
+        struct Bid {
+            uint64 startBlock;
+            uint24 startCumulativeMps;
+            uint64 exitedBlock;
+            uint256 maxPrice;
+            address owner;
+            uint256 amountQ96;
+            uint256 tokensFilled;
+        }
+
+        function bids(uint256 bidId) external view returns (Bid memory bid);
         function submitBid(
             uint256 maxPrice,
             uint128 amount,
@@ -48,10 +113,15 @@ sol! {
     contract ValidationHook {
         function CONTRIBUTOR_PERIOD_END_BLOCK() external view returns (uint256);
         function MAX_PURCHASE_LIMIT() external view returns (uint256);
+        function SOULBOUND() external view returns (address);
         function totalPurchased(address sender)
             external
             view
             returns (uint256 totalPurchased);
+
+        error ContributorPeriodNotEnded();
+        error MissingSoulboundToken(address sender);
+        error PurchaseLimitExceeded(address sender, uint256 totalPurchased, uint256 maxPurchaseLimit);
     }
 }
 
@@ -63,16 +133,17 @@ sol! {
     }
 }
 
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    contract ERC20 {
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     init_logging()?;
-    let config = Config::from_env()?;
-    let provider = ProviderBuilder::new()
-        .wallet(config.signer.clone())
-        .connect_with(&config.transport)
-        .await?;
-    AuctionBot::build_with_provider(provider, config)
-        .await?
-        .run()
-        .await
+    Cli::parse().execute().await
 }
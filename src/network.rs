@@ -0,0 +1,190 @@
+use alloy::primitives::{Address, address};
+use eyre::{Result, eyre};
+use std::{fmt, str::FromStr};
+
+const MAINNET_CHAIN_ID: u64 = 1;
+const SEPOLIA_CHAIN_ID: u64 = 11_155_111;
+
+const MAINNET_CCA_ADDRESS: Address = address!("0x608c4e792C65f5527B3f70715deA44d3b302F4Ee");
+const MAINNET_HOOK_ADDRESS: Address = address!("0x2DD6e0E331DE9743635590F6c8BC5038374CAc9D");
+const MAINNET_SOULBOUND_ADDRESS: Address = address!("0xBf3CF56c587F5e833337200536A52E171EF29A09");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Sepolia,
+    Custom,
+}
+
+impl FromStr for Network {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mainnet" | "ethereum" => Ok(Self::Mainnet),
+            "sepolia" => Ok(Self::Sepolia),
+            "custom" => Ok(Self::Custom),
+            other => Err(eyre!(
+                "unknown network {other} (expected mainnet, sepolia or custom)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Mainnet => "mainnet",
+            Self::Sepolia => "sepolia",
+            Self::Custom => "custom",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Auction address and chain a network profile expects to run against.
+///
+/// The hook and soulbound contracts are discovered from the CCA; when a profile
+/// pins them, the discovered addresses must match.
+#[derive(Debug, Clone)]
+pub struct NetworkProfile {
+    pub network: Network,
+    pub chain_id: Option<u64>,
+    pub cca: Address,
+    pub hook: Option<Address>,
+    pub soulbound: Option<Address>,
+}
+
+/// Per-field overrides applied on top of a network preset.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOverrides {
+    pub chain_id: Option<u64>,
+    pub cca: Option<Address>,
+    pub hook: Option<Address>,
+    pub soulbound: Option<Address>,
+}
+
+impl NetworkProfile {
+    pub fn resolve(network: Network, overrides: NetworkOverrides) -> Result<Self> {
+        let preset = Preset::for_network(network);
+
+        let cca = overrides.cca.or(preset.cca).ok_or_else(|| {
+            eyre!("network {network} has no default CCA_ADDRESS, set it explicitly")
+        })?;
+
+        Ok(Self {
+            network,
+            chain_id: overrides.chain_id.or(preset.chain_id),
+            cca,
+            hook: overrides.hook.or(preset.hook),
+            soulbound: overrides.soulbound.or(preset.soulbound),
+        })
+    }
+
+    pub fn ensure_chain_id(&self, actual: u64) -> Result<()> {
+        match self.chain_id {
+            Some(expected) if expected != actual => Err(eyre!(
+                "network {} expects chain id {expected} but RPC endpoint reports {actual}",
+                self.network
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn ensure_contracts(&self, hook: Address, soulbound: Address) -> Result<()> {
+        ensure_pinned(self.network, "validation hook", self.hook, hook)?;
+        ensure_pinned(self.network, "soulbound", self.soulbound, soulbound)
+    }
+}
+
+fn ensure_pinned(
+    network: Network,
+    name: &str,
+    expected: Option<Address>,
+    actual: Address,
+) -> Result<()> {
+    match expected {
+        Some(expected) if expected != actual => Err(eyre!(
+            "auction {name} is {actual} but network {network} expects {expected}"
+        )),
+        _ => Ok(()),
+    }
+}
+
+struct Preset {
+    chain_id: Option<u64>,
+    cca: Option<Address>,
+    hook: Option<Address>,
+    soulbound: Option<Address>,
+}
+
+impl Preset {
+    fn for_network(network: Network) -> Self {
+        match network {
+            Network::Mainnet => Self {
+                chain_id: Some(MAINNET_CHAIN_ID),
+                cca: Some(MAINNET_CCA_ADDRESS),
+                hook: Some(MAINNET_HOOK_ADDRESS),
+                soulbound: Some(MAINNET_SOULBOUND_ADDRESS),
+            },
+            // No canonical Sepolia deployment yet, so only the chain is pinned.
+            Network::Sepolia => Self {
+                chain_id: Some(SEPOLIA_CHAIN_ID),
+                cca: None,
+                hook: None,
+                soulbound: None,
+            },
+            Network::Custom => Self {
+                chain_id: None,
+                cca: None,
+                hook: None,
+                soulbound: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAINNET_CCA_ADDRESS, Network, NetworkOverrides, NetworkProfile};
+    use alloy::primitives::Address;
+
+    #[test]
+    fn mainnet_preset_is_complete() {
+        let profile =
+            NetworkProfile::resolve(Network::Mainnet, NetworkOverrides::default()).unwrap();
+        assert_eq!(profile.cca, MAINNET_CCA_ADDRESS);
+        assert_eq!(profile.chain_id, Some(1));
+        assert!(profile.ensure_chain_id(1).is_ok());
+        assert!(profile.ensure_chain_id(11_155_111).is_err());
+    }
+
+    #[test]
+    fn custom_requires_cca_address() {
+        assert!(NetworkProfile::resolve(Network::Custom, NetworkOverrides::default()).is_err());
+
+        let overrides = NetworkOverrides {
+            cca: Some(Address::with_last_byte(1)),
+            ..NetworkOverrides::default()
+        };
+        let profile = NetworkProfile::resolve(Network::Custom, overrides).unwrap();
+        assert!(profile.ensure_chain_id(31_337).is_ok());
+        assert!(
+            profile
+                .ensure_contracts(Address::with_last_byte(2), Address::with_last_byte(3))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_hook() {
+        let profile =
+            NetworkProfile::resolve(Network::Mainnet, NetworkOverrides::default()).unwrap();
+        let soulbound = profile.soulbound.unwrap();
+        assert!(
+            profile
+                .ensure_contracts(Address::with_last_byte(2), soulbound)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,580 @@
+use crate::{
+    auction::{AuctionPhase, AuctionWindow},
+    blocks::{BlockProducer, check_receipt},
+    failure::BidFailure,
+    registry::BidRegistry,
+    schedule::BidWindow,
+};
+use alloy::{
+    consensus::TxEnvelope,
+    eips::eip2718::Decodable2718,
+    network::TransactionBuilder,
+    primitives::{Address, B256, Bytes, U256, keccak256},
+    providers::Provider,
+    rpc::{client::BuiltInConnectionString, types::TransactionRequest},
+};
+use eyre::{Result, WrapErr, eyre};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{error, info, warn};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Gas limit for offline-signed bids; estimating is not possible before the
+/// public track opens because `submitBid` reverts until then.
+pub const DEFAULT_OFFLINE_GAS_LIMIT: u64 = 600_000;
+
+/// Chain state the signed transactions were built against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub chain_id: u64,
+    pub block: u64,
+    pub cca: Address,
+    #[serde(default)]
+    pub start_block: U256,
+    pub contributor_period_end_block: U256,
+    pub end_block: U256,
+    #[serde(default)]
+    pub claim_block: U256,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub gas_limit: u64,
+}
+
+/// One signed bid, stored as a raw transaction file next to the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedBid {
+    pub label: String,
+    pub sender: Address,
+    pub owner: Address,
+    pub nonce: u64,
+    pub amount: u128,
+    pub max_bid: U256,
+    pub prev_tick_price: U256,
+    pub window: BidWindow,
+    pub tx_hash: B256,
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub snapshot: Snapshot,
+    pub bids: Vec<SignedBid>,
+}
+
+impl Manifest {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let contents = fs::read_to_string(&path)
+            .wrap_err(format!("failed to read manifest at {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .wrap_err(format!("failed to parse manifest at {}", path.display()))
+    }
+
+    fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(MANIFEST_FILE);
+        let file =
+            fs::File::create(&path).wrap_err(format!("failed to create {}", path.display()))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    fn window(&self) -> AuctionWindow {
+        AuctionWindow {
+            start_block: self.snapshot.start_block,
+            contributor_period_end_block: self.snapshot.contributor_period_end_block,
+            end_block: self.snapshot.end_block,
+            claim_block: self.snapshot.claim_block,
+        }
+    }
+}
+
+/// Fixes nonces, fees and `prevTickPrice` hints from the current chain state,
+/// signs every planned bid and writes the raw transactions plus a manifest to
+/// `out_dir` without broadcasting anything.
+pub async fn sign_bids<P>(
+    provider: &P,
+    registry: &mut BidRegistry<P>,
+    cca: Address,
+    gas_limit: u64,
+    out_dir: &Path,
+) -> Result<Manifest>
+where
+    P: Provider + Clone,
+{
+    let chain_id = provider.get_chain_id().await?;
+    let block = provider.get_block_number().await?;
+    let fees = provider.estimate_eip1559_fees().await?;
+    let window = *registry.window();
+    let snapshot = Snapshot {
+        chain_id,
+        block,
+        cca,
+        start_block: window.start_block,
+        contributor_period_end_block: window.contributor_period_end_block,
+        end_block: window.end_block,
+        claim_block: window.claim_block,
+        max_fee_per_gas: fees.max_fee_per_gas,
+        max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+        gas_limit,
+    };
+    info!(chain_id, block, "chain snapshot taken");
+
+    fs::create_dir_all(out_dir).wrap_err(format!("failed to create {}", out_dir.display()))?;
+
+    registry.resolve_hints(None).await?;
+
+    let mut nonces = NonceBook::default();
+    let mut bids = Vec::new();
+    for (idx, tracked) in registry.bids_mut().iter_mut().enumerate() {
+        let bid = tracked.bid_params().clone();
+        let nonce = nonces.next(provider, bid.sender).await?;
+
+        let context = tracked.context_mut();
+        let submit = context.prepare_submit_bid().await?;
+        let mut tx = context
+            .build_transaction(&submit)
+            .await?
+            .with_nonce(nonce)
+            .with_chain_id(chain_id)
+            .with_gas_limit(gas_limit);
+        if tx.max_fee_per_gas.is_none() {
+            tx.set_max_fee_per_gas(snapshot.max_fee_per_gas);
+            tx.set_max_priority_fee_per_gas(snapshot.max_priority_fee_per_gas);
+        }
+        let raw = context.sign_transaction(tx).await?;
+
+        let file = format!("{:02}-{}.tx", idx + 1, file_safe(&bid.label));
+        let path = out_dir.join(&file);
+        fs::write(&path, raw.to_string())
+            .wrap_err(format!("failed to write {}", path.display()))?;
+        let tx_hash = keccak256(&raw);
+        info!(label = %bid.label, sender = ?bid.sender, nonce, tx = ?tx_hash, "bid signed");
+
+        bids.push(SignedBid {
+            label: bid.label,
+            sender: bid.sender,
+            owner: bid.owner,
+            nonce,
+            amount: bid.amount,
+            max_bid: submit.max_price,
+            prev_tick_price: submit.prev_tick_price,
+            window: bid.window,
+            tx_hash,
+            file,
+        });
+    }
+
+    let manifest = Manifest { snapshot, bids };
+    manifest.write(out_dir)?;
+    Ok(manifest)
+}
+
+/// Hands out consecutive nonces per sender, starting from the sender's pending
+/// nonce the first time it is seen.
+#[derive(Debug, Default)]
+struct NonceBook {
+    next: HashMap<Address, u64>,
+}
+
+impl NonceBook {
+    async fn next<P>(&mut self, provider: &P, sender: Address) -> Result<u64>
+    where
+        P: Provider,
+    {
+        let nonce = match self.next.get(&sender) {
+            Some(nonce) => *nonce,
+            None => provider.get_transaction_count(sender).pending().await?,
+        };
+        self.next.insert(sender, nonce + 1);
+        Ok(nonce)
+    }
+}
+
+/// Outcome of pushing one signed bid.
+#[derive(Debug, Clone)]
+pub enum BroadcastState {
+    Pending,
+    /// Included and accepted by the auction.
+    Sent(B256),
+    /// Included, so the nonce is used, but the auction rejected the bid.
+    Reverted(B256, BidFailure),
+    Failed(String),
+    /// The bid's window closed, or an earlier nonce of its sender never landed.
+    Skipped(String),
+}
+
+/// What to do with a pending signed bid in the current block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Send,
+    /// Not yet: its window has not opened or an earlier nonce is held back.
+    Wait,
+    Skip(String),
+}
+
+/// Decides the step for a pending `bid` at `block`. Once a bid of a sender
+/// cannot go out, `held` keeps that sender's later nonces back for the block.
+fn step(bid: &SignedBid, held: &mut HashSet<Address>, block: U256) -> Step {
+    if held.contains(&bid.sender) {
+        return Step::Wait;
+    }
+    if bid.window.is_closed(block) {
+        held.insert(bid.sender);
+        return Step::Skip("window closed".to_string());
+    }
+    if !bid.window.is_open(block) {
+        held.insert(bid.sender);
+        return Step::Wait;
+    }
+    Step::Send
+}
+
+type Entry = (SignedBid, Bytes, BroadcastState);
+
+/// Sends every bid due at `block` through `send`, in manifest order, then
+/// strands the later bids of any sender whose nonce failed or was skipped.
+async fn broadcast_block<F>(entries: &mut [Entry], block: U256, mut send: F)
+where
+    F: AsyncFnMut(&SignedBid, &Bytes) -> Result<BroadcastState>,
+{
+    let mut held: HashSet<Address> = HashSet::new();
+    for (bid, raw, state) in entries.iter_mut() {
+        if !matches!(state, BroadcastState::Pending) {
+            continue;
+        }
+        match step(bid, &mut held, block) {
+            Step::Wait => continue,
+            Step::Skip(reason) => {
+                warn!(label = %bid.label, nonce = bid.nonce, reason = %reason, "bid window closed before broadcast");
+                *state = BroadcastState::Skipped(reason);
+                continue;
+            }
+            Step::Send => {}
+        }
+
+        *state = match send(bid, raw).await {
+            Ok(BroadcastState::Reverted(tx_hash, failure)) => {
+                error!(label = %bid.label, nonce = bid.nonce, tx = ?tx_hash, failure = %failure, "signed bid reverted");
+                BroadcastState::Reverted(tx_hash, failure)
+            }
+            Ok(state) => {
+                info!(label = %bid.label, nonce = bid.nonce, "signed bid accepted");
+                state
+            }
+            Err(err) => {
+                error!(label = %bid.label, nonce = bid.nonce, error = ?err, "broadcast failed");
+                held.insert(bid.sender);
+                BroadcastState::Failed(format!("{err:#}"))
+            }
+        };
+    }
+
+    // A failed or skipped nonce strands every later bid from the same sender.
+    let stranded: HashSet<Address> = entries
+        .iter()
+        .filter(|(_, _, state)| {
+            matches!(
+                state,
+                BroadcastState::Failed(_) | BroadcastState::Skipped(_)
+            )
+        })
+        .map(|(bid, _, _)| bid.sender)
+        .collect();
+    for (bid, _, state) in entries.iter_mut() {
+        if matches!(state, BroadcastState::Pending) && stranded.contains(&bid.sender) {
+            *state = BroadcastState::Skipped("earlier nonce did not land".to_string());
+        }
+    }
+}
+
+/// Waits for the public track, then pushes the signed transactions in
+/// `dir`, honouring each bid's block window and per-sender nonce order.
+pub async fn broadcast<P>(
+    provider: P,
+    transport: &BuiltInConnectionString,
+    dir: &Path,
+) -> Result<Vec<(SignedBid, BroadcastState)>>
+where
+    P: Provider + Clone + Send + Sync + Unpin + 'static,
+{
+    let manifest = Manifest::load(dir)?;
+    let chain_id = provider.get_chain_id().await?;
+    if chain_id != manifest.snapshot.chain_id {
+        return Err(eyre!(
+            "RPC endpoint reports chain ID {chain_id}, transactions were signed for {}",
+            manifest.snapshot.chain_id
+        ));
+    }
+
+    let mut entries = Vec::with_capacity(manifest.bids.len());
+    for bid in &manifest.bids {
+        entries.push((bid.clone(), read_raw(dir, bid)?, BroadcastState::Pending));
+    }
+    let window = manifest.window();
+    let cca = manifest.snapshot.cca;
+    let mut blocks = BlockProducer::new(provider.clone(), transport).await?;
+
+    while let Some(header) = blocks.next().await {
+        let header = header?;
+        let block_number = U256::from(header.number);
+        match window.phase(block_number) {
+            AuctionPhase::NotStarted | AuctionPhase::ContributorTrack => {
+                info!(
+                    block = header.number,
+                    blocks_remaining = %window.contributor_period_end_block - block_number,
+                    "waiting for public track"
+                );
+                continue;
+            }
+            AuctionPhase::Ended | AuctionPhase::Claimable => {
+                warn!(
+                    block = header.number,
+                    "auction ended before every bid was sent"
+                );
+                break;
+            }
+            AuctionPhase::PublicTrack => {}
+        }
+
+        broadcast_block(&mut entries, block_number, async |bid, raw| {
+            send_raw(&provider, raw, bid, cca).await
+        })
+        .await;
+
+        if entries
+            .iter()
+            .all(|(_, _, state)| !matches!(state, BroadcastState::Pending))
+        {
+            break;
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(bid, _, state)| (bid, state))
+        .collect())
+}
+
+/// Sends one signed bid and checks its receipt. A bid that is included but
+/// reverts is not an error here: its nonce is used, so later bids can follow.
+async fn send_raw<P>(
+    provider: &P,
+    raw: &Bytes,
+    bid: &SignedBid,
+    cca: Address,
+) -> Result<BroadcastState>
+where
+    P: Provider,
+{
+    let envelope = TxEnvelope::decode_2718_exact(raw)
+        .map_err(|err| eyre!("{} is not a valid signed transaction: {err}", bid.file))?;
+    let replay = TransactionRequest::from_transaction_with_sender(envelope, bid.sender);
+    let pending = provider.send_raw_transaction(raw).await?;
+    let receipt = pending.get_receipt().await?;
+    Ok(match check_receipt(provider, &receipt, cca, replay).await {
+        Ok(_) => BroadcastState::Sent(receipt.transaction_hash),
+        Err(err) => {
+            BroadcastState::Reverted(receipt.transaction_hash, BidFailure::from_report(&err))
+        }
+    })
+}
+
+fn read_raw(dir: &Path, bid: &SignedBid) -> Result<Bytes> {
+    let path: PathBuf = dir.join(&bid.file);
+    let contents =
+        fs::read_to_string(&path).wrap_err(format!("failed to read {}", path.display()))?;
+    let raw: Bytes = contents
+        .trim()
+        .parse()
+        .map_err(|err| eyre!("{} is not a hex-encoded transaction: {err}", path.display()))?;
+    if keccak256(&raw) != bid.tx_hash {
+        return Err(eyre!(
+            "{} does not match the manifest hash {}",
+            path.display(),
+            bid.tx_hash
+        ));
+    }
+    Ok(raw)
+}
+
+fn file_safe(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BroadcastState, Entry, Manifest, NonceBook, SignedBid, Snapshot, broadcast_block, read_raw,
+    };
+    use crate::schedule::BidWindow;
+    use alloy::{
+        primitives::{Address, B256, Bytes, U64, U256, keccak256},
+        providers::{ProviderBuilder, mock::Asserter},
+    };
+    use eyre::eyre;
+    use std::fs;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+    const CAROL: Address = Address::repeat_byte(0xc4);
+    const DAVE: Address = Address::repeat_byte(0xd4);
+
+    fn signed(sender: Address, nonce: u64, window: BidWindow) -> SignedBid {
+        let label = format!("{}-{nonce}", sender.0[0]);
+        SignedBid {
+            file: format!("{label}.tx"),
+            label,
+            sender,
+            owner: sender,
+            nonce,
+            amount: 1_000,
+            max_bid: U256::from(200u64),
+            prev_tick_price: U256::from(100u64),
+            window,
+            tx_hash: B256::repeat_byte(nonce as u8),
+        }
+    }
+
+    fn states(entries: &[Entry]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|(_, _, state)| match state {
+                BroadcastState::Pending => "pending".to_string(),
+                BroadcastState::Sent(_) => "sent".to_string(),
+                BroadcastState::Reverted(..) => "reverted".to_string(),
+                BroadcastState::Failed(_) => "failed".to_string(),
+                BroadcastState::Skipped(reason) => format!("skipped: {reason}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn assigns_consecutive_nonces_per_sender() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(5u64));
+        asserter.push_success(&U64::from(9u64));
+
+        let mut nonces = NonceBook::default();
+        let mut next = async |sender| nonces.next(&provider, sender).await.unwrap();
+        assert_eq!(next(ALICE).await, 5);
+        assert_eq!(next(BOB).await, 9);
+        assert_eq!(next(ALICE).await, 6);
+        assert_eq!(next(BOB).await, 10);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[test]
+    fn manifest_roundtrips_and_raw_files_match_their_hash() {
+        let dir = std::env::temp_dir().join(format!("cca-offline-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let raw = Bytes::from(vec![0x02, 0xf8, 0x6c, 0x01]);
+        let mut bid = signed(ALICE, 3, BidWindow::default());
+        bid.tx_hash = keccak256(&raw);
+        fs::write(dir.join(&bid.file), raw.to_string()).unwrap();
+        let manifest = Manifest {
+            snapshot: Snapshot {
+                chain_id: 1,
+                block: 100,
+                cca: Address::repeat_byte(0x01),
+                start_block: U256::from(90u64),
+                contributor_period_end_block: U256::from(200u64),
+                end_block: U256::from(300u64),
+                claim_block: U256::from(350u64),
+                max_fee_per_gas: 40_000_000_000,
+                max_priority_fee_per_gas: 2_000_000_000,
+                gas_limit: 600_000,
+            },
+            bids: vec![bid.clone()],
+        };
+        manifest.write(&dir).unwrap();
+
+        let loaded = Manifest::load(&dir).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&manifest).unwrap()
+        );
+        assert_eq!(read_raw(&dir, &loaded.bids[0]).unwrap(), raw);
+
+        fs::write(dir.join(&bid.file), "0x02f86c02").unwrap();
+        let err = read_raw(&dir, &bid).unwrap_err();
+        assert!(err.to_string().contains("does not match the manifest hash"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn later_nonces_wait_for_or_are_stranded_by_earlier_ones() {
+        let closed = BidWindow {
+            not_before: None,
+            not_after: Some(U256::from(5u64)),
+        };
+        let later = BidWindow {
+            not_before: Some(U256::from(20u64)),
+            not_after: None,
+        };
+        let open = BidWindow::default();
+        let mut entries: Vec<Entry> = [
+            signed(ALICE, 0, open),
+            signed(ALICE, 1, open),
+            signed(BOB, 0, closed),
+            signed(BOB, 1, open),
+            signed(CAROL, 0, later),
+            signed(CAROL, 1, open),
+            signed(DAVE, 0, open),
+            signed(DAVE, 1, open),
+        ]
+        .into_iter()
+        .map(|bid| (bid, Bytes::new(), BroadcastState::Pending))
+        .collect();
+
+        let mut sent = Vec::new();
+        let mut send = async |bid: &SignedBid, _: &Bytes| {
+            sent.push((bid.sender, bid.nonce));
+            if bid.sender == ALICE {
+                return Err(eyre!("nonce too low"));
+            }
+            Ok(BroadcastState::Sent(bid.tx_hash))
+        };
+
+        broadcast_block(&mut entries, U256::from(10u64), &mut send).await;
+        assert_eq!(
+            states(&entries),
+            [
+                "failed",
+                "skipped: earlier nonce did not land",
+                "skipped: window closed",
+                "skipped: earlier nonce did not land",
+                "pending",
+                "pending",
+                "sent",
+                "sent",
+            ]
+        );
+
+        broadcast_block(&mut entries, U256::from(20u64), &mut send).await;
+        assert_eq!(&states(&entries)[4..6], ["sent", "sent"]);
+        assert_eq!(
+            sent,
+            [(ALICE, 0), (DAVE, 0), (DAVE, 1), (CAROL, 0), (CAROL, 1)]
+        );
+    }
+}
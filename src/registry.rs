@@ -1,16 +1,18 @@
 use crate::{
-    auction::{Auction, AuctionParams},
+    auction::{Auction, AuctionParams, AuctionPhase, AuctionState, AuctionWindow, PhaseTransition},
     blocks::BidContext,
     config::BidParams,
+    failure::{BidFailure, FailureClass},
+    senders::Senders,
     transaction::TxConfig,
+    validate::revalidate_pending,
 };
 use alloy::{
     primitives::{Address, B256, U256},
     providers::Provider,
-    signers::local::PrivateKeySigner,
 };
-use eyre::Result;
-use serde::Serialize;
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
 
 const DEFAULT_MAX_RETRIES: u8 = 3;
 
@@ -20,7 +22,13 @@ where
     P: Provider + Clone,
 {
     bids: Vec<TrackedBid<P>>,
+    auction: Auction<P>,
+    params: AuctionParams,
     window: AuctionWindow,
+    /// Every phase change seen while processing blocks.
+    phases: Vec<PhaseTransition>,
+    /// Latest per-block refresh, shared by every bid.
+    state: Option<AuctionState>,
 }
 
 impl<P> BidRegistry<P>
@@ -31,13 +39,10 @@ where
         auction: Auction<P>,
         params: AuctionParams,
         bids: Vec<PlannedBid>,
-        signer: PrivateKeySigner,
+        senders: &Senders,
         cca_addr: Address,
     ) -> Result<Self> {
-        let window = AuctionWindow {
-            contributor_period_end_block: params.contributor_period_end_block,
-            end_block: params.end_block,
-        };
+        let window = params.window();
 
         let tracked = bids
             .into_iter()
@@ -46,36 +51,152 @@ where
                     params: bid_params,
                     tx_config,
                     max_retries,
+                    ..
                 } = planned;
+                let sender = senders.get(bid_params.sender).ok_or_else(|| {
+                    eyre!(
+                        "bid {} sender {} has no signer",
+                        bid_params.label,
+                        bid_params.sender
+                    )
+                })?;
                 let context = BidContext::new(
                     auction.clone(),
-                    params.clone(),
+                    params.floor_price,
                     bid_params.clone(),
-                    signer.clone(),
+                    sender.signer.clone(),
                     tx_config,
                     cca_addr,
                 );
-                TrackedBid {
+                Ok(TrackedBid {
                     bid_params,
                     context,
                     state: BidState::Pending,
                     attempts: 0,
                     max_retries,
-                    last_error: None,
-                }
+                    last_failure: None,
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
             bids: tracked,
+            auction,
+            params,
             window,
+            phases: Vec::new(),
+            state: None,
         })
     }
 
+    /// Refreshes the shared tick cache up to `block` before hints are computed.
+    pub async fn sync_ticks(&self, block: u64) -> Result<()> {
+        self.auction
+            .sync_ticks(self.params.floor_price, block)
+            .await
+    }
+
+    /// Re-reads the auction state at `block` and closes every pending bid it
+    /// rules out. Returns the label and reason of each bid closed.
+    pub async fn refresh_state(&mut self, block: u64) -> Result<Vec<(String, String)>> {
+        let mut senders: Vec<Address> = Vec::new();
+        for bid in self.bids.iter().filter(|bid| bid.is_pending()) {
+            if !senders.contains(&bid.bid_params.sender) {
+                senders.push(bid.bid_params.sender);
+            }
+        }
+        let state = self.auction.load_state(&senders, block).await?;
+
+        let pending: Vec<usize> = self
+            .bids
+            .iter()
+            .enumerate()
+            .filter(|(_, bid)| bid.is_pending())
+            .map(|(idx, _)| idx)
+            .collect();
+        let pending_params: Vec<&BidParams> = pending
+            .iter()
+            .map(|idx| &self.bids[*idx].bid_params)
+            .collect();
+        let invalid = revalidate_pending(&self.params, &state, &pending_params);
+
+        let mut closed = Vec::with_capacity(invalid.len());
+        for (pos, reason) in invalid {
+            let tracked = &mut self.bids[pending[pos]];
+            tracked.mark_invalid(reason.clone());
+            closed.push((tracked.bid_params.label.clone(), reason));
+        }
+        self.state = Some(state);
+        Ok(closed)
+    }
+
+    pub fn state(&self) -> Option<&AuctionState> {
+        self.state.as_ref()
+    }
+
+    pub fn params(&self) -> &AuctionParams {
+        &self.params
+    }
+
+    /// Max bids of every bid still pending or already placed.
+    pub fn live_max_bids(&self) -> impl Iterator<Item = U256> + '_ {
+        self.bids
+            .iter()
+            .filter(|bid| matches!(bid.state, BidState::Pending | BidState::Submitted { .. }))
+            .map(|bid| bid.bid_params.max_bid)
+    }
+
+    /// Resolves `prevTickPrice` hints for the pending bids in one batch, limited
+    /// to bids whose window is open at `open_at` when given. If this fails,
+    /// every bid looks up its own hint instead.
+    pub async fn resolve_hints(&mut self, open_at: Option<U256>) -> Result<()> {
+        for bid in self.bids.iter_mut() {
+            bid.context.set_prev_tick_hint(None);
+        }
+
+        let selected: Vec<usize> = self
+            .bids
+            .iter()
+            .enumerate()
+            .filter(|(_, bid)| {
+                bid.is_pending() && open_at.is_none_or(|block| bid.bid_params.window.is_open(block))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        let prices: Vec<U256> = selected
+            .iter()
+            .map(|idx| self.bids[*idx].bid_params.max_bid)
+            .collect();
+
+        let hints = self
+            .auction
+            .prev_tick_prices(self.params.floor_price, &prices)
+            .await?;
+        for (idx, hint) in selected.into_iter().zip(hints) {
+            self.bids[idx].context.set_prev_tick_hint(Some(hint));
+        }
+        Ok(())
+    }
+
     pub fn window(&self) -> &AuctionWindow {
         &self.window
     }
 
+    /// Records the phase at `block`, returning the transition if it changed.
+    pub fn observe_phase(&mut self, block: u64, phase: AuctionPhase) -> Option<PhaseTransition> {
+        let from = self.phases.last().map(|transition| transition.to);
+        if from == Some(phase) {
+            return None;
+        }
+        let transition = PhaseTransition {
+            block,
+            from,
+            to: phase,
+        };
+        self.phases.push(transition);
+        Some(transition)
+    }
+
     pub fn bids_mut(&mut self) -> &mut [TrackedBid<P>] {
         &mut self.bids
     }
@@ -87,6 +208,7 @@ where
     pub fn summary(&self) -> BidSummary {
         let mut submitted = 0;
         let mut failed = 0;
+        let mut expired = 0;
         let mut pending = 0;
 
         let outcomes = self
@@ -99,24 +221,52 @@ where
                         BidOutcomeState::Pending {
                             attempts: bid.attempts,
                             max_retries: bid.max_retries,
-                            last_error: bid.last_error.clone(),
+                            last_error: bid.last_error(),
                         }
                     }
-                    BidState::Submitted { tx_hash } => {
+                    BidState::Submitted {
+                        tx_hash,
+                        bid_id,
+                        mismatch,
+                    } => {
                         submitted += 1;
-                        BidOutcomeState::Submitted { tx_hash: *tx_hash }
+                        BidOutcomeState::Submitted {
+                            tx_hash: *tx_hash,
+                            bid_id: Some(*bid_id),
+                            mismatch: mismatch.clone(),
+                        }
+                    }
+                    BidState::Unconfirmed { tx_hash, error } => {
+                        submitted += 1;
+                        BidOutcomeState::Unconfirmed {
+                            tx_hash: *tx_hash,
+                            error: error.clone(),
+                        }
                     }
-                    BidState::Failed { error } => {
+                    BidState::Failed { failure } => {
                         failed += 1;
                         BidOutcomeState::Failed {
-                            error: error.clone(),
+                            error: failure.to_string(),
+                            failure: Some(failure.clone()),
+                        }
+                    }
+                    BidState::Expired { block } => {
+                        expired += 1;
+                        BidOutcomeState::Expired {
+                            block: *block,
+                            attempts: bid.attempts,
+                            last_error: bid.last_error(),
                         }
                     }
                 };
 
                 BidOutcome {
+                    label: bid.bid_params.label.clone(),
                     owner: bid.bid_params.owner,
+                    sender: bid.bid_params.sender,
                     amount: bid.bid_params.amount,
+                    max_bid: bid.bid_params.max_bid,
+                    price: self.params.price_scale.format_q96(bid.bid_params.max_bid),
                     state,
                 }
             })
@@ -125,7 +275,9 @@ where
         BidSummary {
             submitted,
             failed,
+            expired,
             pending,
+            phases: self.phases.clone(),
             outcomes,
         }
     }
@@ -147,25 +299,17 @@ impl PlannedBid {
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_tx_config(mut self, tx_config: TxConfig) -> Self {
         self.tx_config = Some(tx_config);
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_max_retries(mut self, max_retries: u8) -> Self {
         self.max_retries = max_retries;
         self
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct AuctionWindow {
-    pub contributor_period_end_block: U256,
-    pub end_block: U256,
-}
-
 #[derive(Debug)]
 pub struct TrackedBid<P>
 where
@@ -176,7 +320,7 @@ where
     state: BidState,
     attempts: u8,
     max_retries: u8,
-    last_error: Option<String>,
+    last_failure: Option<BidFailure>,
 }
 
 impl<P> TrackedBid<P>
@@ -195,6 +339,10 @@ where
         self.max_retries
     }
 
+    fn last_error(&self) -> Option<String> {
+        self.last_failure.as_ref().map(BidFailure::to_string)
+    }
+
     pub fn is_pending(&self) -> bool {
         matches!(self.state, BidState::Pending)
     }
@@ -202,7 +350,10 @@ where
     pub fn is_complete(&self) -> bool {
         matches!(
             self.state,
-            BidState::Submitted { .. } | BidState::Failed { .. }
+            BidState::Submitted { .. }
+                | BidState::Unconfirmed { .. }
+                | BidState::Failed { .. }
+                | BidState::Expired { .. }
         )
     }
 
@@ -210,16 +361,42 @@ where
         &mut self.context
     }
 
-    pub fn mark_submitted(&mut self, tx_hash: B256) {
-        self.state = BidState::Submitted { tx_hash };
-        self.last_error = None;
+    pub fn mark_submitted(&mut self, tx_hash: B256, bid_id: U256, mismatch: Option<String>) {
+        self.state = BidState::Submitted {
+            tx_hash,
+            bid_id,
+            mismatch,
+        };
+        self.last_failure = None;
+    }
+
+    /// Closes a bid whose transaction succeeded without a `BidSubmitted` event.
+    /// It is never resent, since the transaction may already hold a bid.
+    pub fn mark_unconfirmed(&mut self, tx_hash: B256, error: String) {
+        self.state = BidState::Unconfirmed { tx_hash, error };
     }
 
-    pub fn record_failure(&mut self, error: String) -> RetryStatus {
+    /// Closes a pending bid that can no longer be accepted.
+    pub fn mark_invalid(&mut self, reason: String) {
+        let failure = BidFailure::invalidated(reason);
+        self.last_failure = Some(failure.clone());
+        self.state = BidState::Failed { failure };
+    }
+
+    /// Closes a bid whose block window ended before it could be submitted.
+    pub fn mark_expired(&mut self, block: U256) {
+        self.state = BidState::Expired { block };
+    }
+
+    /// Counts a failed attempt; fatal failures close the bid at once.
+    pub fn record_failure(&mut self, failure: BidFailure) -> RetryStatus {
         self.attempts = self.attempts.saturating_add(1);
-        self.last_error = Some(error.clone());
-        if self.attempts >= self.max_retries {
-            self.state = BidState::Failed { error };
+        self.last_failure = Some(failure.clone());
+        if failure.class == FailureClass::Fatal {
+            self.state = BidState::Failed { failure };
+            RetryStatus::Fatal
+        } else if self.attempts >= self.max_retries {
+            self.state = BidState::Failed { failure };
             RetryStatus::Exhausted
         } else {
             RetryStatus::Retrying(self.attempts)
@@ -230,32 +407,63 @@ where
 #[derive(Debug)]
 pub enum BidState {
     Pending,
-    Submitted { tx_hash: B256 },
-    Failed { error: String },
+    Submitted {
+        tx_hash: B256,
+        bid_id: U256,
+        /// Set when the bid read back from the CCA differs from the plan.
+        mismatch: Option<String>,
+    },
+    /// Included and successful, but no `BidSubmitted` event was found.
+    Unconfirmed {
+        tx_hash: B256,
+        error: String,
+    },
+    Failed {
+        failure: BidFailure,
+    },
+    Expired {
+        block: U256,
+    },
 }
 
 #[derive(Debug)]
 pub enum RetryStatus {
     Retrying(u8),
     Exhausted,
+    /// The auction rejected the bid in a way a resend cannot fix.
+    Fatal,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BidSummary {
+    /// Bids whose transaction was included and succeeded, confirmed or not.
     pub submitted: usize,
     pub failed: usize,
+    #[serde(default)]
+    pub expired: usize,
     pub pending: usize,
+    #[serde(default)]
+    pub phases: Vec<PhaseTransition>,
     pub outcomes: Vec<BidOutcome>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BidOutcome {
+    #[serde(default)]
+    pub label: String,
     pub owner: Address,
+    #[serde(default)]
+    pub sender: Address,
     pub amount: u128,
+    #[serde(default)]
+    pub max_bid: U256,
+    /// `max_bid` in currency per whole token.
+    #[serde(default)]
+    pub price: String,
     pub state: BidOutcomeState,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BidOutcomeState {
     Pending {
         attempts: u8,
@@ -264,8 +472,24 @@ pub enum BidOutcomeState {
     },
     Submitted {
         tx_hash: B256,
+        /// CCA bid ID; absent in summaries written before it was recorded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bid_id: Option<U256>,
+        /// How the bid read back from the CCA differs from the plan, if it does.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mismatch: Option<String>,
     },
+    /// Included and successful, but without a `BidSubmitted` event; not resent.
+    Unconfirmed { tx_hash: B256, error: String },
     Failed {
         error: String,
+        /// Decoded revert, absent in summaries written before decoding existed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        failure: Option<BidFailure>,
+    },
+    Expired {
+        block: U256,
+        attempts: u8,
+        last_error: Option<String>,
     },
 }
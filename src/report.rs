@@ -0,0 +1,290 @@
+use crate::{
+    auction::{AuctionParams, AuctionPhase, AuctionState},
+    config::BidParams,
+    failure::BidFailure,
+    logging::PersistedSummary,
+    offline::{BroadcastState, Manifest, SignedBid},
+    registry::{BidOutcomeState, PlannedBid},
+    safe::SafeBatch,
+    validate::SenderBudget,
+};
+use alloy::primitives::U256;
+use eyre::Result;
+use std::path::Path;
+
+pub fn print_plan(params: &AuctionParams, planned: &[PlannedBid], budgets: &[SenderBudget]) {
+    let scale = params.price_scale;
+    println!(
+        "floor price {} ({})  tick spacing {}  max bid price {}",
+        scale.format_q96(params.floor_price),
+        params.floor_price,
+        params.tick_spacing,
+        params.max_bid_price
+    );
+    println!("{} bid(s) planned", planned.len());
+    for bid in planned {
+        // Zero spacing never resolves a bid, but the plan must not divide by it.
+        let ticks_above_floor = (bid.params.max_bid - params.floor_price)
+            .checked_div(params.tick_spacing)
+            .map(|ticks| format!(", floor + {ticks} ticks"))
+            .unwrap_or_default();
+        println!(
+            "  {:<8} sender {}  owner {}  amount {}  price {} (Q96 {}{})  {}  retries {}",
+            bid.params.label,
+            bid.params.sender,
+            bid.params.owner,
+            bid.params.amount,
+            scale.format_q96(bid.params.max_bid),
+            bid.params.max_bid,
+            ticks_above_floor,
+            bid.params.window,
+            bid.max_retries
+        );
+        if bid.params.requested_max_bid != bid.params.max_bid {
+            let (direction, delta) = if bid.params.max_bid > bid.params.requested_max_bid {
+                ("up", bid.params.max_bid - bid.params.requested_max_bid)
+            } else {
+                ("down", bid.params.requested_max_bid - bid.params.max_bid)
+            };
+            println!(
+                "           rounding {}: moved {direction} by {} (Q96 {delta}) from {}",
+                bid.params.rounding,
+                scale.format_q96(delta),
+                scale.format_q96(bid.params.requested_max_bid)
+            );
+        }
+    }
+    for budget in budgets {
+        println!(
+            "sender {}  {} bid(s): {}  purchased {} + planned {} / limit {}  remaining {}",
+            budget.sender,
+            budget.labels.len(),
+            budget.labels.join(", "),
+            budget.total_purchased,
+            budget.planned,
+            budget.max_purchase_limit,
+            budget.remaining()
+        );
+    }
+}
+
+pub fn print_status(
+    block_number: u64,
+    phase: AuctionPhase,
+    params: &AuctionParams,
+    state: &AuctionState,
+) {
+    let block = U256::from(block_number);
+    println!("block                  {block_number}");
+    println!("phase                  {phase}");
+    println!(
+        "start block            {}{}",
+        params.start_block,
+        blocks_until(block, params.start_block)
+    );
+    println!(
+        "contributor period end {}{}",
+        params.contributor_period_end_block,
+        blocks_until(block, params.contributor_period_end_block)
+    );
+    println!(
+        "end block              {}{}",
+        params.end_block,
+        blocks_until(block, params.end_block)
+    );
+    println!(
+        "claim block            {}{}",
+        params.claim_block,
+        blocks_until(block, params.claim_block)
+    );
+    println!(
+        "floor price            {} ({})",
+        params.price_scale.format_q96(params.floor_price),
+        params.floor_price
+    );
+    println!(
+        "clearing price         {} ({})",
+        params.price_scale.format_q96(state.clearing_price),
+        state.clearing_price
+    );
+    println!(
+        "currency raised        {}",
+        params.currency.format_amount(state.currency_raised)
+    );
+    println!(
+        "latest checkpoint      block {}, clearing price {}",
+        state.checkpoint.block,
+        params
+            .price_scale
+            .format_q96(state.checkpoint.clearing_price)
+    );
+    println!(
+        "supply released        {} of {} tokens",
+        params.price_scale.format_tokens(state.released_supply()),
+        params
+            .price_scale
+            .format_tokens(U256::from(state.total_supply))
+    );
+    println!("tick spacing           {}", params.tick_spacing);
+    println!("max bid price          {}", params.max_bid_price);
+    println!("max purchase limit     {}", params.max_purchase_limit);
+    for allocation in &params.allocations {
+        println!(
+            "sender {}  purchased {}  soulbound token {}",
+            allocation.sender,
+            allocation.total_purchased,
+            if allocation.has_any_token {
+                "yes"
+            } else {
+                "no"
+            }
+        );
+    }
+}
+
+pub fn print_summary(persisted: &PersistedSummary) {
+    let summary = &persisted.summary;
+    println!("reason     {:?}", persisted.reason);
+    println!(
+        "submitted  {}  failed {}  expired {}  pending {}",
+        summary.submitted, summary.failed, summary.expired, summary.pending
+    );
+    for transition in &summary.phases {
+        println!("  block {:<10} {}", transition.block, transition.to);
+    }
+    for outcome in &summary.outcomes {
+        let state = match &outcome.state {
+            BidOutcomeState::Submitted {
+                tx_hash,
+                bid_id,
+                mismatch,
+            } => {
+                let submitted = match bid_id {
+                    Some(bid_id) => format!("submitted bid {bid_id} tx {tx_hash}"),
+                    None => format!("submitted tx {tx_hash}"),
+                };
+                match mismatch {
+                    Some(mismatch) => format!("{submitted}, mismatch: {mismatch}"),
+                    None => submitted,
+                }
+            }
+            BidOutcomeState::Unconfirmed { tx_hash, error } => {
+                format!("included tx {tx_hash}, unconfirmed: {error}")
+            }
+            BidOutcomeState::Failed { error, .. } => format!("failed: {error}"),
+            BidOutcomeState::Expired {
+                block, last_error, ..
+            } => match last_error {
+                Some(error) => format!("expired at block {block}: {error}"),
+                None => format!("expired at block {block}"),
+            },
+            BidOutcomeState::Pending {
+                attempts,
+                max_retries,
+                last_error,
+            } => match last_error {
+                Some(error) => format!("pending ({attempts}/{max_retries}): {error}"),
+                None => format!("pending ({attempts}/{max_retries})"),
+            },
+        };
+        println!(
+            "  {:<8} sender {}  owner {}  amount {}  price {} (Q96 {})  {state}",
+            outcome.label,
+            outcome.sender,
+            outcome.owner,
+            outcome.amount,
+            outcome.price,
+            outcome.max_bid
+        );
+    }
+}
+
+pub fn print_signed(manifest: &Manifest, dir: &Path) {
+    let snapshot = &manifest.snapshot;
+    println!(
+        "snapshot at block {}  chain {}  max fee {}  priority fee {}  gas limit {}",
+        snapshot.block,
+        snapshot.chain_id,
+        snapshot.max_fee_per_gas,
+        snapshot.max_priority_fee_per_gas,
+        snapshot.gas_limit
+    );
+    println!(
+        "{} bid(s) signed into {}",
+        manifest.bids.len(),
+        dir.display()
+    );
+    for bid in &manifest.bids {
+        println!(
+            "  {:<8} sender {}  nonce {}  amount {}  prev tick {}  {}  tx {}",
+            bid.label,
+            bid.sender,
+            bid.nonce,
+            bid.amount,
+            bid.prev_tick_price,
+            bid.file,
+            bid.tx_hash
+        );
+    }
+}
+
+pub fn print_safe_batch(batch: &SafeBatch, path: &Path) {
+    println!(
+        "{} bid(s) from Safe {} written to {}",
+        batch.transactions.len(),
+        batch.meta.created_from_safe_address,
+        path.display()
+    );
+    for tx in &batch.transactions {
+        println!(
+            "  {:<8} value {}  prev tick {}  calldata {} bytes",
+            tx.label,
+            tx.value,
+            tx.prev_tick_price,
+            tx.data.len()
+        );
+    }
+}
+
+pub fn print_broadcast(results: &[(SignedBid, BroadcastState)]) {
+    for (bid, state) in results {
+        let state = match state {
+            BroadcastState::Pending => "not sent".to_string(),
+            BroadcastState::Sent(tx_hash) => format!("sent tx {tx_hash}"),
+            BroadcastState::Reverted(tx_hash, failure) => {
+                format!("reverted tx {tx_hash}: {failure}")
+            }
+            BroadcastState::Failed(error) => format!("failed: {error}"),
+            BroadcastState::Skipped(reason) => format!("skipped: {reason}"),
+        };
+        println!(
+            "  {:<8} sender {}  nonce {}  {state}",
+            bid.label, bid.sender, bid.nonce
+        );
+    }
+}
+
+pub fn print_simulation(bid: &BidParams, result: &Result<()>) {
+    match result {
+        Ok(()) => println!(
+            "  {:<8} sender {}  owner {}  amount {}  simulation ok",
+            bid.label, bid.sender, bid.owner, bid.amount
+        ),
+        Err(err) => println!(
+            "  {:<8} sender {}  owner {}  amount {}  simulation failed: {}",
+            bid.label,
+            bid.sender,
+            bid.owner,
+            bid.amount,
+            BidFailure::from_report(err)
+        ),
+    }
+}
+
+fn blocks_until(current: U256, target: U256) -> String {
+    if current < target {
+        format!(" (in {} blocks)", target - current)
+    } else {
+        String::new()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{auction::SubmitBidParams, registry::BidRegistry};
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::Provider,
+};
+use eyre::{Result, WrapErr};
+use serde::Serialize;
+use std::{
+    fs::File,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::info;
+
+const BATCH_VERSION: &str = "1.0";
+
+/// `CALL`; `submitBid` is never sent as a `DELEGATECALL`.
+const OPERATION_CALL: u8 = 0;
+
+/// Transaction batch in the Safe Transaction Builder import format.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatch {
+    pub version: &'static str,
+    pub chain_id: String,
+    pub created_at: u128,
+    pub meta: SafeBatchMeta,
+    pub transactions: Vec<SafeTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchMeta {
+    pub name: String,
+    pub description: String,
+    pub created_from_safe_address: Address,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SafeTransaction {
+    pub to: Address,
+    /// Wei, as a decimal string.
+    pub value: String,
+    pub data: Bytes,
+    pub operation: u8,
+    #[serde(skip)]
+    pub label: String,
+    #[serde(skip)]
+    pub prev_tick_price: U256,
+}
+
+/// Turns every planned bid into a `submitBid` call from `safe`, with hints
+/// computed against the current chain state.
+pub async fn build_safe_batch<P>(
+    provider: &P,
+    registry: &mut BidRegistry<P>,
+    safe: Address,
+    cca: Address,
+) -> Result<SafeBatch>
+where
+    P: Provider + Clone,
+{
+    let chain_id = provider.get_chain_id().await?;
+
+    registry.resolve_hints(None).await?;
+
+    let mut transactions = Vec::new();
+    for tracked in registry.bids_mut().iter_mut() {
+        let label = tracked.bid_params().label.clone();
+        let context = tracked.context_mut();
+        let submit = context.prepare_submit_bid().await?;
+        let data = context.bid_calldata(&submit);
+        let tx = SafeTransaction::submit_bid(cca, label, &submit, data);
+        info!(label = %tx.label, prev_tick_price = %tx.prev_tick_price, "bid added to Safe batch");
+        transactions.push(tx);
+    }
+
+    Ok(SafeBatch::new(chain_id, safe, cca, transactions))
+}
+
+impl SafeBatch {
+    fn new(chain_id: u64, safe: Address, cca: Address, transactions: Vec<SafeTransaction>) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+
+        Self {
+            version: BATCH_VERSION,
+            chain_id: chain_id.to_string(),
+            created_at,
+            meta: SafeBatchMeta {
+                name: "CCA bids".to_string(),
+                description: format!("{} submitBid call(s) to {cca}", transactions.len()),
+                created_from_safe_address: safe,
+            },
+            transactions,
+        }
+    }
+}
+
+impl SafeTransaction {
+    fn submit_bid(cca: Address, label: String, submit: &SubmitBidParams, data: Bytes) -> Self {
+        Self {
+            to: cca,
+            value: submit.amount.to_string(),
+            data,
+            operation: OPERATION_CALL,
+            label,
+            prev_tick_price: submit.prev_tick_price,
+        }
+    }
+}
+
+pub fn write_safe_batch(batch: &SafeBatch, path: &Path) -> Result<()> {
+    let file = File::create(path).wrap_err(format!("failed to create {}", path.display()))?;
+    serde_json::to_writer_pretty(file, batch)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SafeBatch, SafeTransaction};
+    use crate::{CCA, auction::SubmitBidParams, signer::SafeAccount, transaction::TxBuilder};
+    use alloy::{
+        primitives::{Address, U256},
+        providers::{ProviderBuilder, mock::Asserter},
+        sol_types::SolCall,
+    };
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn batch_matches_transaction_builder_format() {
+        let safe = Address::repeat_byte(0x5a);
+        let cca = Address::repeat_byte(0xcc);
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(Asserter::new());
+        let builder = TxBuilder::new(provider, Arc::new(SafeAccount::new(safe)), cca, None);
+        let submit = SubmitBidParams {
+            max_price: U256::from(300u64),
+            amount: 5_000,
+            owner: safe,
+            prev_tick_price: U256::from(200u64),
+            hintless: false,
+        };
+        let data = builder.bid_calldata(&submit);
+        let tx = SafeTransaction::submit_bid(cca, "bid-1".to_string(), &submit, data.clone());
+
+        let mut batch =
+            serde_json::to_value(SafeBatch::new(11155111, safe, cca, vec![tx])).unwrap();
+        assert!(batch["createdAt"].is_u64());
+        batch["createdAt"] = json!(0);
+        assert_eq!(
+            batch,
+            json!({
+                "version": "1.0",
+                "chainId": "11155111",
+                "createdAt": 0,
+                "meta": {
+                    "name": "CCA bids",
+                    "description": format!("1 submitBid call(s) to {cca}"),
+                    "createdFromSafeAddress": safe,
+                },
+                "transactions": [{
+                    "to": cca,
+                    "value": "5000",
+                    "data": data,
+                    "operation": 0,
+                }],
+            })
+        );
+
+        let call = CCA::submitBid_1Call::abi_decode(&data).unwrap();
+        assert_eq!(call.maxPrice, submit.max_price);
+        assert_eq!(call.amount, submit.amount);
+        assert_eq!(call.owner, safe);
+        assert_eq!(call.prevTickPrice, submit.prev_tick_price);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::auction::AuctionParams;
+use alloy::primitives::U256;
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Block a bid window edge is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockAnchor {
+    ContributorPeriodEnd,
+    End,
+}
+
+impl BlockAnchor {
+    fn block(self, params: &AuctionParams) -> U256 {
+        match self {
+            Self::ContributorPeriodEnd => params.contributor_period_end_block,
+            Self::End => params.end_block,
+        }
+    }
+}
+
+impl fmt::Display for BlockAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContributorPeriodEnd => f.write_str("contributor_period_end_block"),
+            Self::End => f.write_str("end_block"),
+        }
+    }
+}
+
+/// Absolute block number or an offset from an auction block, e.g. `end_block - 50`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRef {
+    anchor: Option<BlockAnchor>,
+    offset: i64,
+}
+
+impl BlockRef {
+    pub fn resolve(&self, params: &AuctionParams) -> Result<U256> {
+        let base = self
+            .anchor
+            .map_or(U256::ZERO, |anchor| anchor.block(params));
+        let magnitude = U256::from(self.offset.unsigned_abs());
+        if self.offset >= 0 {
+            Ok(base + magnitude)
+        } else {
+            base.checked_sub(magnitude)
+                .ok_or_else(|| eyre!("{self} resolves to a negative block"))
+        }
+    }
+}
+
+impl FromStr for BlockRef {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let compact: String = value.split_whitespace().collect();
+        let invalid = || {
+            eyre!(
+                "{value} is not a valid block (expected a number, or contributor_period_end_block/end_block +/- offset)"
+            )
+        };
+
+        if let Ok(number) = compact.parse::<u64>() {
+            return Ok(Self {
+                anchor: None,
+                offset: i64::try_from(number).map_err(|_| invalid())?,
+            });
+        }
+
+        let split = compact.find(['+', '-']).unwrap_or(compact.len());
+        let (name, rest) = compact.split_at(split);
+        let anchor = match name {
+            "contributor_period_end_block" | "contributor_period_end" => {
+                BlockAnchor::ContributorPeriodEnd
+            }
+            "end_block" | "end" => BlockAnchor::End,
+            _ => return Err(invalid()),
+        };
+
+        let offset = if rest.is_empty() {
+            0
+        } else {
+            let (sign, digits) = rest.split_at(1);
+            // Digits only: `parse` would take a second sign, so `end_block--5`
+            // would silently land after end_block.
+            if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let magnitude = digits
+                .parse::<u64>()
+                .ok()
+                .and_then(|magnitude| i64::try_from(magnitude).ok())
+                .ok_or_else(invalid)?;
+            if sign == "-" { -magnitude } else { magnitude }
+        };
+
+        Ok(Self {
+            anchor: Some(anchor),
+            offset,
+        })
+    }
+}
+
+impl fmt::Display for BlockRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.anchor, self.offset) {
+            (None, offset) => write!(f, "{offset}"),
+            (Some(anchor), 0) => write!(f, "{anchor}"),
+            (Some(anchor), offset) if offset < 0 => write!(f, "{anchor} - {}", -offset),
+            (Some(anchor), offset) => write!(f, "{anchor} + {offset}"),
+        }
+    }
+}
+
+/// Block reference as written in TOML: a bare integer or an expression string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum BlockRefSpec {
+    Number(u64),
+    Expr(String),
+}
+
+impl BlockRefSpec {
+    pub fn parse(&self) -> Result<BlockRef> {
+        match self {
+            Self::Number(number) => BlockRef::from_str(&number.to_string()),
+            Self::Expr(expr) => BlockRef::from_str(expr),
+        }
+    }
+}
+
+/// Unresolved `not_before_block` / `not_after_block` pair from the config.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowSpec {
+    pub not_before: Option<BlockRef>,
+    pub not_after: Option<BlockRef>,
+}
+
+impl WindowSpec {
+    pub fn resolve(&self, params: &AuctionParams) -> Result<BidWindow> {
+        let not_before = self
+            .not_before
+            .map(|block| block.resolve(params))
+            .transpose()?;
+        let not_after = self
+            .not_after
+            .map(|block| block.resolve(params))
+            .transpose()?;
+        Ok(BidWindow {
+            not_before,
+            not_after,
+        })
+    }
+}
+
+/// Inclusive block range a bid may be sent in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BidWindow {
+    pub not_before: Option<U256>,
+    pub not_after: Option<U256>,
+}
+
+impl BidWindow {
+    pub fn is_open(&self, block_number: U256) -> bool {
+        self.not_before.is_none_or(|start| block_number >= start) && !self.is_closed(block_number)
+    }
+
+    pub fn is_closed(&self, block_number: U256) -> bool {
+        self.not_after.is_some_and(|end| block_number > end)
+    }
+}
+
+impl fmt::Display for BidWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.not_before, self.not_after) {
+            (None, None) => f.write_str("any block"),
+            (Some(start), None) => write!(f, "from block {start}"),
+            (None, Some(end)) => write!(f, "until block {end}"),
+            (Some(start), Some(end)) => write!(f, "blocks {start}..={end}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BidWindow, BlockRef};
+    use crate::auction::AuctionParams;
+    use alloy::primitives::U256;
+    use std::str::FromStr;
+
+    fn params() -> AuctionParams {
+        AuctionParams::for_test().with_blocks(1_000, 2_000)
+    }
+
+    #[test]
+    fn resolves_block_expressions() {
+        let params = params();
+        let resolve = |raw: &str| BlockRef::from_str(raw).unwrap().resolve(&params).unwrap();
+        assert_eq!(resolve("1500"), U256::from(1_500u64));
+        assert_eq!(resolve("end_block - 50"), U256::from(1_950u64));
+        assert_eq!(resolve("end_block"), U256::from(2_000u64));
+        assert_eq!(
+            resolve("contributor_period_end_block+10"),
+            U256::from(1_010u64)
+        );
+        assert!(BlockRef::from_str("start_block + 1").is_err());
+        assert!(BlockRef::from_str("end_block - x").is_err());
+        for doubled in [
+            "end_block--5",
+            "end_block+-5",
+            "end_block-+5",
+            "end_block++5",
+        ] {
+            assert!(BlockRef::from_str(doubled).is_err(), "{doubled}");
+        }
+        assert!(
+            BlockRef::from_str("end_block - 5000")
+                .unwrap()
+                .resolve(&params)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn window_edges_are_inclusive() {
+        let window = BidWindow {
+            not_before: Some(U256::from(10u64)),
+            not_after: Some(U256::from(20u64)),
+        };
+        assert!(!window.is_open(U256::from(9u64)));
+        assert!(window.is_open(U256::from(10u64)));
+        assert!(window.is_open(U256::from(20u64)));
+        assert!(window.is_closed(U256::from(21u64)));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::signer::{BidSigner, LocalSigner, RemoteSigner};
+use alloy::{
+    primitives::Address,
+    signers::local::{MnemonicBuilder, coins_bip39::English},
+    transports::http::reqwest::Url,
+};
+use eyre::{Result, eyre};
+use serde::Deserialize;
+use std::{fmt, sync::Arc};
+
+/// An account bids can be sent from.
+#[derive(Debug, Clone)]
+pub struct Sender {
+    pub signer: Arc<dyn BidSigner>,
+    /// Derivation index when the account was derived from `MNEMONIC`.
+    pub index: Option<u32>,
+}
+
+impl Sender {
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+}
+
+impl fmt::Display for Sender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{} (index {index})", self.address()),
+            None => write!(f, "{}", self.address()),
+        }
+    }
+}
+
+/// Every account the bot may sign with; the first one is the default sender.
+#[derive(Debug, Clone)]
+pub struct Senders {
+    senders: Vec<Sender>,
+}
+
+impl Senders {
+    pub fn single(signer: Arc<dyn BidSigner>) -> Self {
+        Self {
+            senders: vec![Sender {
+                signer,
+                index: None,
+            }],
+        }
+    }
+
+    /// One remote-signed sender per address, all served by the signer at `url`.
+    pub fn remote(url: Url, addresses: &[Address]) -> Result<Self> {
+        if addresses.is_empty() {
+            return Err(eyre!(
+                "REMOTE_SIGNER_ADDRESSES must list at least one address"
+            ));
+        }
+        let senders = addresses
+            .iter()
+            .map(|address| Sender {
+                signer: Arc::new(RemoteSigner::new(url.clone(), *address)),
+                index: None,
+            })
+            .collect();
+        Ok(Self { senders })
+    }
+
+    /// Derives one account per index along `m/44'/60'/0'/0/{index}`.
+    pub fn from_mnemonic(phrase: &str, indices: &[u32]) -> Result<Self> {
+        if indices.is_empty() {
+            return Err(eyre!("MNEMONIC_INDICES must list at least one index"));
+        }
+
+        let mut senders: Vec<Sender> = Vec::with_capacity(indices.len());
+        for &index in indices {
+            if senders.iter().any(|sender| sender.index == Some(index)) {
+                return Err(eyre!("MNEMONIC_INDICES lists index {index} twice"));
+            }
+            let signer = MnemonicBuilder::<English>::default()
+                .phrase(phrase)
+                .index(index)
+                .and_then(|builder| builder.build())
+                .map_err(|err| eyre!("failed to derive account {index} from MNEMONIC: {err}"))?;
+            senders.push(Sender {
+                signer: Arc::new(LocalSigner::new(signer)),
+                index: Some(index),
+            });
+        }
+
+        Ok(Self { senders })
+    }
+
+    pub fn default_sender(&self) -> &Sender {
+        &self.senders[0]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Sender> {
+        self.senders.iter()
+    }
+
+    pub fn addresses(&self) -> Vec<Address> {
+        self.senders.iter().map(Sender::address).collect()
+    }
+
+    pub fn get(&self, address: Address) -> Option<&Sender> {
+        self.senders
+            .iter()
+            .find(|sender| sender.address() == address)
+    }
+
+    /// Resolves a bid's `sender` entry to one of the loaded accounts.
+    pub fn resolve(&self, sender: &SenderRef) -> Result<Address> {
+        let found = match sender {
+            SenderRef::Index(index) => self
+                .senders
+                .iter()
+                .find(|sender| sender.index == Some(*index)),
+            SenderRef::Address(address) => self.get(*address),
+        };
+        found.map(Sender::address).ok_or_else(|| match sender {
+            SenderRef::Index(index) => {
+                eyre!("sender index {index} is not listed in MNEMONIC_INDICES")
+            }
+            SenderRef::Address(address) => {
+                eyre!("sender {address} is not one of the configured accounts")
+            }
+        })
+    }
+}
+
+/// A bid's `sender` as written in the config: a derivation index or a
+/// checksummed address.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "SenderSpec")]
+pub enum SenderRef {
+    Index(u32),
+    Address(Address),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SenderSpec {
+    Index(u32),
+    Address(String),
+}
+
+impl TryFrom<SenderSpec> for SenderRef {
+    type Error = String;
+
+    fn try_from(spec: SenderSpec) -> Result<Self, Self::Error> {
+        match spec {
+            SenderSpec::Index(index) => Ok(Self::Index(index)),
+            SenderSpec::Address(raw) => Address::parse_checksummed(raw.trim(), None)
+                .map(Self::Address)
+                .map_err(|_| format!("sender is not a valid checksummed address: {raw}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SenderRef, Senders};
+    use alloy::primitives::address;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn derives_and_resolves_senders() {
+        let senders = Senders::from_mnemonic(PHRASE, &[0, 2]).unwrap();
+        let first = address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        let third = address!("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC");
+
+        assert_eq!(senders.default_sender().address(), first);
+        assert_eq!(senders.resolve(&SenderRef::Index(2)).unwrap(), third);
+        assert_eq!(senders.resolve(&SenderRef::Address(third)).unwrap(), third);
+        assert!(senders.resolve(&SenderRef::Index(1)).is_err());
+        assert!(Senders::from_mnemonic(PHRASE, &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn sender_addresses_must_be_checksummed() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Entry {
+            sender: SenderRef,
+        }
+        let parse = |raw: &str| toml::from_str::<Entry>(&format!("sender = {raw}"));
+
+        let Entry { sender } = parse("2").unwrap();
+        assert!(matches!(sender, SenderRef::Index(2)));
+        let Entry { sender } = parse("\"0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\"").unwrap();
+        assert!(matches!(
+            sender,
+            SenderRef::Address(address)
+                if address == address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        ));
+        assert!(parse("\"0xf39fd6e51aad88F6F4ce6aB8827279cffFb92266\"").is_err());
+    }
+}
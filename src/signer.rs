@@ -0,0 +1,232 @@
+use alloy::{
+    eips::eip2718::Encodable2718,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{Address, Bytes},
+    rpc::{client::RpcClient, types::eth::TransactionRequest},
+    signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
+};
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use serde::Deserialize;
+use std::fmt;
+
+/// Signs fully populated bid transactions for one account.
+#[async_trait]
+pub trait BidSigner: fmt::Debug + Send + Sync {
+    fn address(&self) -> Address;
+
+    /// Returns the EIP-2718 encoded signed transaction, ready for
+    /// `eth_sendRawTransaction`.
+    async fn sign_transaction(&self, tx: TransactionRequest) -> Result<Bytes>;
+}
+
+/// Signs in-process with a key loaded from a keystore, mnemonic or raw key.
+#[derive(Debug, Clone)]
+pub struct LocalSigner {
+    signer: PrivateKeySigner,
+}
+
+impl LocalSigner {
+    pub fn new(signer: PrivateKeySigner) -> Self {
+        Self { signer }
+    }
+}
+
+#[async_trait]
+impl BidSigner for LocalSigner {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    async fn sign_transaction(&self, tx: TransactionRequest) -> Result<Bytes> {
+        let wallet = EthereumWallet::new(self.signer.clone());
+        let envelope = tx
+            .build(&wallet)
+            .await
+            .map_err(|err| eyre!("failed to sign transaction: {err}"))?;
+        Ok(envelope.encoded_2718().into())
+    }
+}
+
+/// Delegates signing to an external JSON-RPC signer (Clef, Web3Signer) through
+/// `eth_signTransaction`, so the key never enters the bot.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: RpcClient,
+    url: Url,
+    address: Address,
+}
+
+impl RemoteSigner {
+    pub fn new(url: Url, address: Address) -> Self {
+        Self {
+            client: RpcClient::new_http(url.clone()),
+            url,
+            address,
+        }
+    }
+}
+
+/// `eth_signTransaction` result: Web3Signer returns the raw transaction, Clef
+/// wraps it alongside the decoded transaction.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SignTransactionResponse {
+    Raw(Bytes),
+    Wrapped { raw: Bytes },
+}
+
+#[async_trait]
+impl BidSigner for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, tx: TransactionRequest) -> Result<Bytes> {
+        let tx = tx.with_from(self.address);
+        let response: SignTransactionResponse = self
+            .client
+            .request("eth_signTransaction", (tx,))
+            .await
+            .map_err(|err| eyre!("remote signer at {} rejected transaction: {err}", self.url))?;
+        Ok(match response {
+            SignTransactionResponse::Raw(raw) | SignTransactionResponse::Wrapped { raw } => raw,
+        })
+    }
+}
+
+/// A Safe multisig used as the bid sender. It has no key here: bids are exported
+/// as a Safe transaction batch and signed by the Safe owners instead.
+#[derive(Debug, Clone)]
+pub struct SafeAccount {
+    address: Address,
+}
+
+impl SafeAccount {
+    pub fn new(address: Address) -> Self {
+        Self { address }
+    }
+}
+
+#[async_trait]
+impl BidSigner for SafeAccount {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, _tx: TransactionRequest) -> Result<Bytes> {
+        Err(eyre!(
+            "Safe {} cannot sign directly, export its bids with the `safe` command",
+            self.address
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BidSigner, LocalSigner, RemoteSigner};
+    use alloy::{
+        network::TransactionBuilder,
+        primitives::{Address, U256},
+        rpc::types::eth::TransactionRequest,
+        signers::local::PrivateKeySigner,
+        transports::http::reqwest::Url,
+    };
+    use serde_json::{Value, json};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// Minimal HTTP JSON-RPC server answering `eth_signTransaction` with `signer`.
+    async fn spawn_stub_signer(signer: LocalSigner, wrapped: bool) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let signer = signer.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let body = loop {
+                        let mut chunk = [0u8; 4096];
+                        let read = socket.read(&mut chunk).await.unwrap();
+                        if read == 0 {
+                            return;
+                        }
+                        buf.extend_from_slice(&chunk[..read]);
+                        let text = String::from_utf8_lossy(&buf).to_string();
+                        let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                            continue;
+                        };
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    };
+
+                    let request: Value = serde_json::from_str(&body).unwrap();
+                    assert_eq!(request["method"], "eth_signTransaction");
+                    let tx: TransactionRequest =
+                        serde_json::from_value(request["params"][0].clone()).unwrap();
+                    let raw = signer.sign_transaction(tx).await.unwrap();
+                    let result = if wrapped {
+                        json!({ "raw": raw, "tx": {} })
+                    } else {
+                        json!(raw)
+                    };
+                    let response =
+                        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                            .to_string();
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                        response.len()
+                    );
+                    socket.write_all(reply.as_bytes()).await.unwrap();
+                });
+            }
+        });
+        url.parse().unwrap()
+    }
+
+    fn request(from: Address) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_from(from)
+            .with_to(Address::repeat_byte(0x11))
+            .with_value(U256::from(1_000u64))
+            .with_nonce(7)
+            .with_chain_id(1)
+            .with_gas_limit(250_000)
+            .with_max_fee_per_gas(40_000_000_000)
+            .with_max_priority_fee_per_gas(2_000_000_000)
+    }
+
+    #[tokio::test]
+    async fn remote_signer_matches_local_signature() {
+        let local = LocalSigner::new(TEST_KEY.parse::<PrivateKeySigner>().unwrap());
+        let expected = local
+            .sign_transaction(request(local.address()))
+            .await
+            .unwrap();
+
+        for wrapped in [false, true] {
+            let url = spawn_stub_signer(local.clone(), wrapped).await;
+            let remote = RemoteSigner::new(url, local.address());
+            let raw = remote
+                .sign_transaction(request(remote.address()))
+                .await
+                .unwrap();
+            assert_eq!(raw, expected);
+        }
+    }
+}
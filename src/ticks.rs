@@ -0,0 +1,104 @@
+use alloy::primitives::U256;
+use eyre::{Result, eyre};
+use std::collections::BTreeSet;
+
+/// Local mirror of the auction's initialized tick prices, kept in sync from
+/// `TickInitialized` logs so prev-tick hints need no per-node `ticks()` calls.
+#[derive(Debug, Clone, Default)]
+pub struct TickCache {
+    prices: BTreeSet<U256>,
+    /// First block whose logs have not been applied yet; `None` until loaded.
+    next_block: Option<u64>,
+}
+
+impl TickCache {
+    /// Seeds the cache from a full tick-list walk taken at `block`.
+    pub fn load(&mut self, prices: impl IntoIterator<Item = U256>, block: u64) {
+        self.prices = prices.into_iter().collect();
+        self.next_block = Some(block);
+    }
+
+    pub fn next_block(&self) -> Option<u64> {
+        self.next_block
+    }
+
+    /// Applies ticks initialized up to and including `block`.
+    pub fn apply(&mut self, prices: impl IntoIterator<Item = U256>, block: u64) {
+        self.prices.extend(prices);
+        self.next_block = Some(block + 1);
+    }
+
+    /// Drops the cache so the next lookup walks the tick list again.
+    pub fn invalidate(&mut self) {
+        self.prices.clear();
+        self.next_block = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Highest initialized tick strictly below `bid_price`, which is the
+    /// `prevTickPrice` hint `submitBid` expects. Bids at the floor use the floor.
+    pub fn prev_tick(&self, floor_price: U256, bid_price: U256) -> Result<U256> {
+        if bid_price < floor_price {
+            return Err(eyre!(
+                "bid price {bid_price} is below floor price {floor_price}"
+            ));
+        }
+        Ok(self
+            .prices
+            .range(..bid_price)
+            .next_back()
+            .copied()
+            .unwrap_or(floor_price)
+            .max(floor_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TickCache;
+    use alloy::primitives::U256;
+
+    fn cache(prices: &[u64]) -> TickCache {
+        let mut cache = TickCache::default();
+        cache.load(prices.iter().map(|price| U256::from(*price)), 100);
+        cache
+    }
+
+    #[test]
+    fn finds_highest_tick_below_price() {
+        let cache = cache(&[10, 20, 40]);
+        let prev = |price: u64| {
+            cache
+                .prev_tick(U256::from(10u64), U256::from(price))
+                .unwrap()
+        };
+        assert_eq!(prev(10), U256::from(10u64));
+        assert_eq!(prev(20), U256::from(10u64));
+        assert_eq!(prev(21), U256::from(20u64));
+        assert_eq!(prev(1_000), U256::from(40u64));
+        assert!(
+            cache
+                .prev_tick(U256::from(10u64), U256::from(9u64))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn applies_new_ticks_incrementally() {
+        let mut cache = cache(&[10, 40]);
+        assert_eq!(cache.next_block(), Some(100));
+        cache.apply([U256::from(30u64)], 105);
+        assert_eq!(cache.next_block(), Some(106));
+        assert_eq!(
+            cache
+                .prev_tick(U256::from(10u64), U256::from(35u64))
+                .unwrap(),
+            U256::from(30u64)
+        );
+        cache.invalidate();
+        assert_eq!(cache.next_block(), None);
+    }
+}
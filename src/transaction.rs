@@ -1,13 +1,13 @@
-use crate::{CCA, auction::SubmitBidParams};
+use crate::{CCA, auction::SubmitBidParams, signer::BidSigner};
 use alloy::{
     network::TransactionBuilder,
     primitives::{Address, Bytes, U256},
     providers::Provider,
     rpc::types::{eth::TransactionRequest, transaction::AccessList},
-    signers::local::PrivateKeySigner,
     sol_types::SolCall,
 };
 use eyre::Result;
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct FeeOverrides {
@@ -16,7 +16,6 @@ pub struct FeeOverrides {
 }
 
 #[derive(Debug, Clone, Default)]
-#[allow(dead_code)]
 pub enum AccessListConfig {
     #[default]
     None,
@@ -30,7 +29,6 @@ pub struct TxConfig {
     pub access_list: AccessListConfig,
 }
 
-#[allow(dead_code)]
 impl TxConfig {
     pub fn new() -> Self {
         Self::default()
@@ -52,15 +50,6 @@ impl TxConfig {
         self.access_list = config;
         self
     }
-
-    pub fn generate_access_list(self) -> Self {
-        self.with_access_list(AccessListConfig::Generate)
-    }
-
-    pub fn provided_access_list(mut self, list: AccessList) -> Self {
-        self.access_list = AccessListConfig::Provided(list);
-        self
-    }
 }
 
 pub struct TxBuilder<P>
@@ -68,7 +57,7 @@ where
     P: Provider + Clone,
 {
     provider: P,
-    signer: PrivateKeySigner,
+    signer: Arc<dyn BidSigner>,
     cca: Address,
     config: Option<TxConfig>,
 }
@@ -79,7 +68,7 @@ where
 {
     pub fn new(
         provider: P,
-        signer: PrivateKeySigner,
+        signer: Arc<dyn BidSigner>,
         cca: Address,
         config: Option<TxConfig>,
     ) -> Self {
@@ -101,7 +90,42 @@ where
         self.apply_config(tx).await
     }
 
-    fn bid_calldata(&self, bid: &SubmitBidParams) -> Bytes {
+    /// Fills nonce, gas, fees and chain ID the request does not already carry,
+    /// then signs it with the sender's signer.
+    pub async fn sign(&self, tx: TransactionRequest) -> Result<Bytes> {
+        let from = self.signer.address();
+        let mut tx = tx.with_from(from);
+
+        if tx.nonce.is_none() {
+            tx.set_nonce(self.provider.get_transaction_count(from).pending().await?);
+        }
+        if tx.chain_id.is_none() {
+            tx.set_chain_id(self.provider.get_chain_id().await?);
+        }
+        if tx.max_fee_per_gas.is_none() || tx.max_priority_fee_per_gas.is_none() {
+            let fees = self.provider.estimate_eip1559_fees().await?;
+            tx.set_max_fee_per_gas(fees.max_fee_per_gas);
+            tx.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
+        if tx.gas.is_none() {
+            tx.set_gas_limit(self.provider.estimate_gas(tx.clone()).await?);
+        }
+
+        self.signer.sign_transaction(tx).await
+    }
+
+    pub fn bid_calldata(&self, bid: &SubmitBidParams) -> Bytes {
+        if bid.hintless {
+            return Bytes::from(
+                CCA::submitBid_0Call {
+                    maxPrice: bid.max_price,
+                    amount: bid.amount,
+                    owner: bid.owner,
+                    hookData: Bytes::new(),
+                }
+                .abi_encode(),
+            );
+        }
         Bytes::from(
             CCA::submitBid_1Call {
                 maxPrice: bid.max_price,
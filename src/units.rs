@@ -0,0 +1,400 @@
+use alloy::primitives::{U256, U512};
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Fractional digits shown when formatting a Q96 price for humans.
+const DISPLAY_PRECISION: u32 = 18;
+
+/// Most decimals a token or currency may have: 10^77 is the largest power of
+/// ten that fits in a `uint256`.
+pub const MAX_DECIMALS: u8 = 77;
+
+/// Rejects the on-chain decimals of `name` if they exceed [`MAX_DECIMALS`].
+pub fn check_decimals(name: &str, decimals: u8) -> Result<u8> {
+    if decimals > MAX_DECIMALS {
+        return Err(eyre!(
+            "{name} reports {decimals} decimals, more than the supported {MAX_DECIMALS}"
+        ));
+    }
+    Ok(decimals)
+}
+
+/// Non-negative decimal number kept as an integer mantissa and a base-10 scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decimal {
+    mantissa: U256,
+    scale: u32,
+}
+
+impl FromStr for Decimal {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let digits_only = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty())
+            || !digits_only(whole)
+            || !digits_only(fraction)
+        {
+            return Err(eyre!("{value} is not a valid decimal number"));
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        let digits = format!("{whole}{fraction}");
+        let mantissa = if digits.is_empty() {
+            U256::ZERO
+        } else {
+            U256::from_str_radix(&digits, 10).map_err(|_| eyre!("{value} has too many digits"))?
+        };
+
+        Ok(Self {
+            mantissa,
+            scale: fraction.len() as u32,
+        })
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_fixed(U512::from(self.mantissa), self.scale))
+    }
+}
+
+/// Unit an amount in `bids.toml` is denominated in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountUnit {
+    Wei,
+    Gwei,
+    Ether,
+    /// Whole units of an ERC20 currency, named by its symbol.
+    Symbol(String),
+}
+
+impl FromStr for AmountUnit {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(eyre!("amount unit cannot be empty"));
+        }
+        Ok(match value.to_ascii_lowercase().as_str() {
+            "wei" => Self::Wei,
+            "gwei" => Self::Gwei,
+            "ether" | "eth" => Self::Ether,
+            _ => Self::Symbol(value.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for AmountUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wei => f.write_str("wei"),
+            Self::Gwei => f.write_str("gwei"),
+            Self::Ether => f.write_str("ether"),
+            Self::Symbol(symbol) => f.write_str(symbol),
+        }
+    }
+}
+
+/// Currency bids are paid in, as read from the auction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrencyInfo {
+    pub symbol: String,
+    pub decimals: u8,
+    pub native: bool,
+}
+
+impl CurrencyInfo {
+    pub fn native() -> Self {
+        Self {
+            symbol: "ETH".to_string(),
+            decimals: 18,
+            native: true,
+        }
+    }
+
+    /// Formats an amount in base units as whole currency, e.g. `1.5 ETH`.
+    pub fn format_amount(&self, amount: U256) -> String {
+        format!(
+            "{} {}",
+            format_fixed(U512::from(amount), self.decimals as u32),
+            self.symbol
+        )
+    }
+
+    fn unit_decimals(&self, unit: &AmountUnit) -> Result<u32> {
+        match unit {
+            AmountUnit::Wei => Ok(0),
+            AmountUnit::Gwei | AmountUnit::Ether if !self.native => Err(eyre!(
+                "unit {unit} only applies to native ETH, auction currency is {}",
+                self.symbol
+            )),
+            AmountUnit::Gwei => Ok(9),
+            AmountUnit::Ether => Ok(18),
+            AmountUnit::Symbol(symbol) if symbol.eq_ignore_ascii_case(&self.symbol) => {
+                Ok(self.decimals as u32)
+            }
+            AmountUnit::Symbol(symbol) => Err(eyre!(
+                "unit {symbol} does not match auction currency {}",
+                self.symbol
+            )),
+        }
+    }
+}
+
+/// Amount as written in the config: a decimal number with an optional unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmountSpec {
+    value: Decimal,
+    unit: Option<AmountUnit>,
+}
+
+impl FromStr for AmountSpec {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut parts = value.split_whitespace();
+        let number = parts
+            .next()
+            .ok_or_else(|| eyre!("amount cannot be empty"))?;
+        let unit = parts.next().map(AmountUnit::from_str).transpose()?;
+        if parts.next().is_some() {
+            return Err(eyre!(
+                "{value} is not a valid amount (expected \"<number> [unit]\")"
+            ));
+        }
+
+        Ok(Self {
+            value: Decimal::from_str(number)?,
+            unit,
+        })
+    }
+}
+
+impl fmt::Display for AmountSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.unit {
+            Some(unit) => write!(f, "{} {unit}", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl AmountSpec {
+    /// Converts the amount into the currency's base units, applying `default_unit`
+    /// when the amount was written without one.
+    pub fn to_base_units(
+        &self,
+        default_unit: &AmountUnit,
+        currency: &CurrencyInfo,
+    ) -> Result<u128> {
+        let unit = self.unit.as_ref().unwrap_or(default_unit);
+        let decimals = currency.unit_decimals(unit)?;
+        if self.value.scale > decimals {
+            return Err(eyre!(
+                "{} has more decimal places than {unit} allows",
+                self.value
+            ));
+        }
+
+        pow10(decimals - self.value.scale)
+            .and_then(|factor| U512::from(self.value.mantissa).checked_mul(factor))
+            .and_then(|scaled| u128::try_from(scaled).ok())
+            .ok_or_else(|| eyre!("{} {unit} does not fit in a u128", self.value))
+    }
+}
+
+/// Token and currency decimals used to translate between Q96 prices and
+/// human-readable currency-per-token prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceScale {
+    pub token_decimals: u8,
+    pub currency_decimals: u8,
+}
+
+impl PriceScale {
+    /// Converts a currency-per-token price into a Q96 price, rounding down.
+    pub fn to_q96(self, price: &Decimal) -> Result<U256> {
+        let numerator = pow10(self.currency_decimals as u32)
+            .and_then(|unit| U512::from(price.mantissa).checked_mul(unit))
+            .and_then(|value| value.checked_mul(U512::from(1u8) << 96));
+        let denominator = pow10(price.scale)
+            .zip(pow10(self.token_decimals as u32))
+            .and_then(|(scale, unit)| scale.checked_mul(unit));
+        numerator
+            .zip(denominator)
+            .and_then(|(numerator, denominator)| {
+                U256::checked_from_limbs_slice((numerator / denominator).as_limbs())
+            })
+            .ok_or_else(|| eyre!("price {price} overflows a Q96 value"))
+    }
+
+    /// Formats a Q96 price as currency per whole token, or as the raw Q96
+    /// value if the decimals are too large to scale it.
+    pub fn format_q96(self, q96: U256) -> String {
+        let numerator = pow10(self.token_decimals as u32 + DISPLAY_PRECISION)
+            .and_then(|unit| U512::from(q96).checked_mul(unit));
+        let denominator = pow10(self.currency_decimals as u32)
+            .and_then(|unit| unit.checked_mul(U512::from(1u8) << 96));
+        match numerator.zip(denominator) {
+            Some((numerator, denominator)) => {
+                format_fixed(numerator / denominator, DISPLAY_PRECISION)
+            }
+            None => format!("Q96 {q96}"),
+        }
+    }
+
+    /// Formats a token amount in base units as whole tokens.
+    pub fn format_tokens(self, amount: U256) -> String {
+        format_fixed(U512::from(amount), self.token_decimals as u32)
+    }
+}
+
+/// `10^exp`, or `None` if it does not fit in a `U512`.
+fn pow10(exp: u32) -> Option<U512> {
+    U512::from(10u8).checked_pow(U512::from(exp))
+}
+
+fn format_fixed(value: U512, scale: u32) -> String {
+    let digits = value.to_string();
+    let scale = scale as usize;
+    let (whole, fraction) = if digits.len() > scale {
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        (whole.to_string(), fraction.to_string())
+    } else {
+        ("0".to_string(), format!("{digits:0>scale$}"))
+    };
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AmountSpec, AmountUnit, CurrencyInfo, Decimal, PriceScale, check_decimals, pow10};
+    use alloy::primitives::U256;
+    use std::str::FromStr;
+
+    const ETH_TOKEN: PriceScale = PriceScale {
+        token_decimals: 18,
+        currency_decimals: 18,
+    };
+
+    #[test]
+    fn parses_decimals() {
+        assert_eq!(Decimal::from_str("0.0025").unwrap().to_string(), "0.0025");
+        assert_eq!(Decimal::from_str("12").unwrap().to_string(), "12");
+        assert_eq!(Decimal::from_str(".5").unwrap().to_string(), "0.5");
+        assert_eq!(Decimal::from_str("1.500").unwrap().to_string(), "1.5");
+        assert!(Decimal::from_str("").is_err());
+        assert!(Decimal::from_str("-1").is_err());
+        assert!(Decimal::from_str("1e18").is_err());
+    }
+
+    #[test]
+    fn converts_price_to_q96() {
+        let q96 = ETH_TOKEN
+            .to_q96(&Decimal::from_str("0.25").unwrap())
+            .unwrap();
+        assert_eq!(q96, U256::from(1u8) << 94);
+        assert_eq!(ETH_TOKEN.format_q96(q96), "0.25");
+    }
+
+    #[test]
+    fn scales_by_decimals() {
+        let usdc = PriceScale {
+            token_decimals: 18,
+            currency_decimals: 6,
+        };
+        let q96 = usdc.to_q96(&Decimal::from_str("2").unwrap()).unwrap();
+        assert_eq!(
+            q96,
+            (U256::from(2_000_000u64) << 96) / U256::from(10u64).pow(U256::from(18))
+        );
+        assert_eq!(
+            usdc.format_q96(U256::from(2_000_000u64) << 96),
+            "2000000000000000000"
+        );
+        assert_eq!(
+            usdc.format_tokens(U256::from(1_250_000_000_000_000_000u64)),
+            "1.25"
+        );
+    }
+
+    #[test]
+    fn converts_amount_units() {
+        let eth = CurrencyInfo::native();
+        let wei = AmountUnit::Wei;
+        let amount = |raw: &str| AmountSpec::from_str(raw).unwrap();
+
+        assert_eq!(
+            amount("2 ether").to_base_units(&wei, &eth).unwrap(),
+            2_000_000_000_000_000_000
+        );
+        assert_eq!(
+            amount("1500 gwei").to_base_units(&wei, &eth).unwrap(),
+            1_500_000_000_000
+        );
+        assert_eq!(
+            amount("2000000000000000000")
+                .to_base_units(&wei, &eth)
+                .unwrap(),
+            2_000_000_000_000_000_000
+        );
+        assert_eq!(
+            amount("0.5")
+                .to_base_units(&AmountUnit::Ether, &eth)
+                .unwrap(),
+            500_000_000_000_000_000
+        );
+        assert!(amount("0.5").to_base_units(&wei, &eth).is_err());
+        assert!(AmountSpec::from_str("1 ether extra").is_err());
+    }
+
+    #[test]
+    fn scales_token_amounts_by_currency_decimals() {
+        let usdc = CurrencyInfo {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            native: false,
+        };
+        let amount = AmountSpec::from_str("250.5 usdc").unwrap();
+        assert_eq!(
+            amount.to_base_units(&AmountUnit::Wei, &usdc).unwrap(),
+            250_500_000
+        );
+        assert!(
+            AmountSpec::from_str("1 ether")
+                .unwrap()
+                .to_base_units(&AmountUnit::Wei, &usdc)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_decimals_that_overflow() {
+        assert!(pow10(154).is_some());
+        assert!(pow10(155).is_none());
+        assert_eq!(check_decimals("token", 77).unwrap(), 77);
+        assert!(check_decimals("token", 78).is_err());
+
+        let huge = PriceScale {
+            token_decimals: 18,
+            currency_decimals: 255,
+        };
+        assert!(huge.to_q96(&Decimal::from_str("1").unwrap()).is_err());
+        let huge = PriceScale {
+            token_decimals: 255,
+            currency_decimals: 18,
+        };
+        assert_eq!(huge.format_q96(U256::from(5u8)), "Q96 5");
+    }
+}
@@ -1,7 +1,29 @@
-use crate::{auction::AuctionParams, config::BidParams};
-use alloy::primitives::U256;
+use crate::{
+    auction::{AuctionParams, AuctionState},
+    config::BidParams,
+};
+use alloy::primitives::{Address, U256};
 use eyre::{Result, eyre};
 
+/// Planned spend of one sender against its validation hook allocation.
+#[derive(Debug, Clone)]
+pub struct SenderBudget {
+    pub sender: Address,
+    pub has_any_token: bool,
+    pub total_purchased: U256,
+    pub planned: U256,
+    pub max_purchase_limit: U256,
+    pub labels: Vec<String>,
+}
+
+impl SenderBudget {
+    /// Allocation left once every planned bid lands.
+    pub fn remaining(&self) -> U256 {
+        self.max_purchase_limit
+            .saturating_sub(self.total_purchased + self.planned)
+    }
+}
+
 pub struct PreflightValidator<'a> {
     params: &'a AuctionParams,
     bids: &'a [BidParams],
@@ -12,13 +34,32 @@ impl<'a> PreflightValidator<'a> {
         Self { params, bids }
     }
 
-    pub fn run(&self) -> Result<()> {
+    /// Checks every bid and returns the per-sender budgets the bids were
+    /// checked against, in order of each sender's first bid.
+    pub fn run(&self) -> Result<Vec<SenderBudget>> {
+        self.ensure_native_currency()?;
         for (idx, bid) in self.bids.iter().enumerate() {
             self.ensure_amount_positive(idx, bid)?;
             self.ensure_max_price_within_bounds(idx, bid)?;
+            self.ensure_window_reachable(idx, bid)?;
+        }
+        let budgets = self.ensure_within_purchase_limit()?;
+        for budget in &budgets {
+            self.ensure_has_soulbound_token(budget)?;
+        }
+        Ok(budgets)
+    }
+
+    /// Bids are paid as `msg.value`, which the CCA rejects with
+    /// `CurrencyIsNotNative` when the auction is priced in an ERC20.
+    fn ensure_native_currency(&self) -> Result<()> {
+        let currency = &self.params.currency;
+        if !currency.native {
+            return Err(eyre!(
+                "auction currency is the ERC20 {}, but bids can only be paid in native ETH",
+                currency.symbol
+            ));
         }
-        self.ensure_within_purchase_limit()?;
-        self.ensure_has_soulbound_token()?;
         Ok(())
     }
 
@@ -34,41 +75,269 @@ impl<'a> PreflightValidator<'a> {
     }
 
     fn ensure_max_price_within_bounds(&self, idx: usize, bid: &BidParams) -> Result<()> {
-        if bid.max_bid > self.params.max_bid_price {
+        // Alignment clamps to the cap, so check the price the bid asked for.
+        if bid.requested_max_bid > self.params.max_bid_price {
             let bid_no = idx + 1;
             return Err(eyre!(
                 "bid #{bid_no} (owner {}) MAX_BID_PRICE ({}) exceeds cap ({})",
                 bid.owner,
-                bid.max_bid,
+                bid.requested_max_bid,
                 self.params.max_bid_price
             ));
         }
         Ok(())
     }
 
-    fn ensure_within_purchase_limit(&self) -> Result<()> {
-        let mut running_total = self.params.total_purchased;
+    fn ensure_window_reachable(&self, idx: usize, bid: &BidParams) -> Result<()> {
+        let bid_no = idx + 1;
+        let window = bid.window;
+        if let (Some(start), Some(end)) = (window.not_before, window.not_after)
+            && start > end
+        {
+            return Err(eyre!(
+                "bid #{bid_no} (owner {}) not_before_block {start} is after not_after_block {end}",
+                bid.owner
+            ));
+        }
+        if let Some(end) = window.not_after
+            && end < self.params.contributor_period_end_block
+        {
+            return Err(eyre!(
+                "bid #{bid_no} (owner {}) not_after_block {end} closes before the public track opens at {}",
+                bid.owner,
+                self.params.contributor_period_end_block
+            ));
+        }
+        if let Some(start) = window.not_before
+            && start >= self.params.end_block
+        {
+            return Err(eyre!(
+                "bid #{bid_no} (owner {}) not_before_block {start} opens after the auction ends at {}",
+                bid.owner,
+                self.params.end_block
+            ));
+        }
+        Ok(())
+    }
+
+    fn ensure_within_purchase_limit(&self) -> Result<Vec<SenderBudget>> {
+        let mut budgets: Vec<SenderBudget> = Vec::new();
 
         for (idx, bid) in self.bids.iter().enumerate() {
-            running_total += U256::from(bid.amount);
+            let bid_no = idx + 1;
+            let pos = match budgets
+                .iter()
+                .position(|budget| budget.sender == bid.sender)
+            {
+                Some(pos) => pos,
+                None => {
+                    let allocation = self.params.allocation(bid.sender).ok_or_else(|| {
+                        eyre!(
+                            "bid #{bid_no} (owner {}) sender {} has no loaded allocation",
+                            bid.owner,
+                            bid.sender
+                        )
+                    })?;
+                    budgets.push(SenderBudget {
+                        sender: bid.sender,
+                        has_any_token: allocation.has_any_token,
+                        total_purchased: allocation.total_purchased,
+                        planned: U256::ZERO,
+                        max_purchase_limit: self.params.max_purchase_limit,
+                        labels: Vec::new(),
+                    });
+                    budgets.len() - 1
+                }
+            };
+
+            let budget = &mut budgets[pos];
+            budget.planned += U256::from(bid.amount);
+            budget.labels.push(bid.label.clone());
+            let running_total = budget.total_purchased + budget.planned;
             if running_total > self.params.max_purchase_limit {
-                let bid_no = idx + 1;
                 return Err(eyre!(
-                    "bids exceed allocation: bid #{bid_no} (owner {}) pushes total {} over cap {}",
+                    "bids exceed allocation: bid #{bid_no} (owner {}) pushes sender {} total {} over cap {}",
                     bid.owner,
+                    bid.sender,
                     running_total,
                     self.params.max_purchase_limit
                 ));
             }
         }
 
-        Ok(())
+        Ok(budgets)
     }
 
-    fn ensure_has_soulbound_token(&self) -> Result<()> {
-        if !self.params.has_any_token {
-            return Err(eyre!("sender ineligible: missing required soulbound token"));
+    fn ensure_has_soulbound_token(&self, budget: &SenderBudget) -> Result<()> {
+        if !budget.has_any_token {
+            return Err(eyre!(
+                "sender {} ineligible: missing required soulbound token (bids {})",
+                budget.sender,
+                budget.labels.join(", ")
+            ));
         }
         Ok(())
     }
 }
+
+/// Re-checks pending bids, in order, against the latest auction state. Returns
+/// the index and reason for every bid that can no longer be accepted.
+pub fn revalidate_pending(
+    params: &AuctionParams,
+    state: &AuctionState,
+    pending: &[&BidParams],
+) -> Vec<(usize, String)> {
+    let mut committed: Vec<(Address, U256)> = Vec::new();
+    let mut invalid = Vec::new();
+
+    for (idx, bid) in pending.iter().enumerate() {
+        if bid.max_bid <= state.clearing_price {
+            invalid.push((
+                idx,
+                format!(
+                    "clearing price {} reached max bid {}",
+                    state.clearing_price, bid.max_bid
+                ),
+            ));
+            continue;
+        }
+
+        let Some(allocation) = state.allocation(bid.sender) else {
+            continue;
+        };
+        if !allocation.has_any_token {
+            invalid.push((
+                idx,
+                format!("sender {} no longer holds a soulbound token", bid.sender),
+            ));
+            continue;
+        }
+
+        let pos = match committed
+            .iter()
+            .position(|(sender, _)| *sender == bid.sender)
+        {
+            Some(pos) => pos,
+            None => {
+                committed.push((bid.sender, allocation.total_purchased));
+                committed.len() - 1
+            }
+        };
+        let running_total = committed[pos].1 + U256::from(bid.amount);
+        if running_total > params.max_purchase_limit {
+            invalid.push((
+                idx,
+                format!(
+                    "sender {} total {running_total} would exceed cap {}",
+                    bid.sender, params.max_purchase_limit
+                ),
+            ));
+            continue;
+        }
+        committed[pos].1 = running_total;
+    }
+
+    invalid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PreflightValidator, revalidate_pending};
+    use crate::{
+        auction::{AuctionParams, AuctionState, SenderAllocation},
+        config::{BidParams, TxSettings},
+        units::CurrencyInfo,
+    };
+    use alloy::primitives::{Address, U256, address};
+
+    const ALICE: Address = address!("0x00000000000000000000000000000000000000a1");
+    const BOB: Address = address!("0x00000000000000000000000000000000000000b0");
+
+    fn params(bob_eligible: bool) -> AuctionParams {
+        AuctionParams::for_test()
+            .with_blocks(0, 10)
+            .with_allocations(
+                U256::from(100u64),
+                vec![
+                    SenderAllocation {
+                        sender: ALICE,
+                        total_purchased: U256::from(40u64),
+                        has_any_token: true,
+                    },
+                    SenderAllocation {
+                        sender: BOB,
+                        total_purchased: U256::ZERO,
+                        has_any_token: bob_eligible,
+                    },
+                ],
+            )
+    }
+
+    fn bid(label: &str, sender: Address, amount: u128) -> BidParams {
+        BidParams {
+            label: label.to_string(),
+            max_bid: U256::from(2u64),
+            requested_max_bid: U256::from(2u64),
+            amount,
+            owner: sender,
+            sender,
+            tx: TxSettings::default(),
+            window: Default::default(),
+            rounding: Default::default(),
+        }
+    }
+
+    #[test]
+    fn checks_purchase_limit_per_sender() {
+        let params = params(true);
+        let bids = [bid("a", ALICE, 60), bid("b", BOB, 90), bid("c", BOB, 10)];
+        let budgets = PreflightValidator::new(&params, &bids).run().unwrap();
+        assert_eq!(budgets.len(), 2);
+        assert_eq!(budgets[0].remaining(), U256::ZERO);
+        assert_eq!(budgets[1].planned, U256::from(100u64));
+        assert_eq!(budgets[1].labels, ["b", "c"]);
+
+        let over = [bid("a", ALICE, 61), bid("b", BOB, 90)];
+        assert!(PreflightValidator::new(&params, &over).run().is_err());
+    }
+
+    #[test]
+    fn rejects_ineligible_sender() {
+        let params = params(false);
+        let bids = [bid("a", ALICE, 10), bid("b", BOB, 10)];
+        let err = PreflightValidator::new(&params, &bids).run().unwrap_err();
+        assert!(err.to_string().contains("ineligible"));
+    }
+
+    #[test]
+    fn rejects_erc20_currencies() {
+        let mut params = params(true);
+        params.currency = CurrencyInfo {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            native: false,
+        };
+        let bids = [bid("a", ALICE, 10)];
+        let err = PreflightValidator::new(&params, &bids).run().unwrap_err();
+        assert!(err.to_string().contains("ERC20 USDC"));
+    }
+
+    #[test]
+    fn revalidates_pending_bids_against_latest_state() {
+        let params = params(true);
+        let mut state = AuctionState::for_test(&params, 5, U256::from(1u64));
+        let bids = [bid("a", ALICE, 30), bid("b", ALICE, 30), bid("c", BOB, 10)];
+        let pending: Vec<_> = bids.iter().collect();
+        assert!(revalidate_pending(&params, &state, &pending).is_empty());
+
+        // Alice bought elsewhere, so only her first bid still fits.
+        state.allocations[0].total_purchased = U256::from(50u64);
+        state.allocations[1].has_any_token = false;
+        let invalid = revalidate_pending(&params, &state, &pending);
+        let indices: Vec<_> = invalid.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(indices, [1, 2]);
+
+        state.clearing_price = U256::from(2u64);
+        assert_eq!(revalidate_pending(&params, &state, &pending).len(), 3);
+    }
+}